@@ -0,0 +1,25 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+// Builds a single computed node that reads every signal up front,
+// so the body is evaluated with every pattern bound to the current value of its signal
+pub fn combine(
+    signals: &[TokenStream],
+    patterns: &[TokenStream],
+    body: TokenStream,
+) -> TokenStream {
+    let first = signals.first().expect("At least one signal");
+    let captured: Vec<_> = (0..signals.len())
+        .map(|idx| format_ident!("__viste_signal_{}", idx))
+        .collect();
+    quote! {{
+        #(let #captured = ::std::clone::Clone::clone(&#signals);)*
+        ::viste_reactive::computed(
+            &::viste_reactive::ValueSignal::signal(&#first).world(),
+            move |__viste_cx| {
+                #(let #patterns = __viste_cx.get(&#captured);)*
+                #body
+            },
+        )
+    }}
+}
@@ -0,0 +1,44 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Fields};
+
+pub fn lenses(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "Lens can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Lens can only be derived for structs",
+            ))
+        }
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let lenses = fields.iter().map(|field| {
+        let field_name = field.ident.as_ref().expect("Named fields have an ident");
+        let field_type = &field.ty;
+        let vis = &field.vis;
+        let lens_name = format_ident!("{}_lens", field_name);
+        quote! {
+            #vis fn #lens_name() -> ::viste_reactive::lens::Lens<Self, #field_type> {
+                ::viste_reactive::lens::Lens {
+                    get: |s| ::std::clone::Clone::clone(&s.#field_name),
+                    set: |s, v| s.#field_name = v,
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#lenses)*
+        }
+    })
+}
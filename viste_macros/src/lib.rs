@@ -0,0 +1,47 @@
+//! Derives and macros for viste_reactive.
+//!
+//! `signal!` turns an expression over signals into a new ValueSignal. Every signal used in the
+//! expression is marked with `$`, everything else is captured like in a `move` closure:
+//!
+//! ```ignore
+//! let (set_a, a) = mutable(&world, 1);
+//! let (_set_b, b) = mutable(&world, 2);
+//! let offset = 10;
+//! let sum = signal!($a + $b * 2 + offset);
+//! ```
+//!
+//! `$a` stands for the current value of the ValueSignal `a` in scope. Each signal is read once per
+//! evaluation, no matter how often it is marked, and the result is a single node depending on all of them.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, Error};
+
+mod combine;
+mod lens;
+mod signal;
+
+// Generates a `<field>_lens()` function for every field, returning a viste_reactive::lens::Lens
+#[proc_macro_derive(Lens)]
+pub fn derive_lens(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    lens::lenses(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+// Generates a `<Name>Signals` struct holding one ValueSignal per field, which can be combined into a signal of the struct
+#[proc_macro_derive(Signals)]
+pub fn derive_signals(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    signal::signals_struct(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+// signal!($a + $b * 2) builds a ValueSignal from the expression, every `$name` refers to a ValueSignal in scope, see the crate docs
+#[proc_macro]
+pub fn signal(input: TokenStream) -> TokenStream {
+    signal::signal_expression(input.into())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
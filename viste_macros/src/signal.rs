@@ -0,0 +1,96 @@
+use crate::combine::combine;
+use proc_macro2::{Group, Ident, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{Data, DeriveInput, Error, Fields};
+
+pub fn signal_expression(input: TokenStream) -> Result<TokenStream, Error> {
+    let mut signals = Vec::new();
+    let body = replace_signals(input, &mut signals)?;
+    if signals.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "signal! needs at least one `$signal` in its expression",
+        ));
+    }
+    let refs: Vec<TokenStream> = signals.iter().map(ToTokens::to_token_stream).collect();
+    Ok(combine(&refs, &refs, quote!({ #body })))
+}
+
+// Replaces every `$name` with `name` and records each name once, in order of appearance
+fn replace_signals(input: TokenStream, signals: &mut Vec<Ident>) -> Result<TokenStream, Error> {
+    let mut output = TokenStream::new();
+    let mut tokens = input.into_iter();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(p) if p.as_char() == '$' => match tokens.next() {
+                Some(TokenTree::Ident(name)) => {
+                    if !signals.contains(&name) {
+                        signals.push(name.clone());
+                    }
+                    output.extend(Some(TokenTree::Ident(name)));
+                }
+                _ => return Err(Error::new(p.span(), "Expected a signal name after `$`")),
+            },
+            TokenTree::Group(g) => {
+                let mut replaced = Group::new(g.delimiter(), replace_signals(g.stream(), signals)?);
+                replaced.set_span(g.span());
+                output.extend(Some(TokenTree::Group(replaced)));
+            }
+            other => output.extend(Some(other)),
+        }
+    }
+    Ok(output)
+}
+
+pub fn signals_struct(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) if !fields.named.is_empty() => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "Signals can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Signals can only be derived for structs",
+            ))
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "Signals can not be derived for generic structs",
+        ));
+    }
+    let name = &input.ident;
+    let vis = &input.vis;
+    let signals_name = format_ident!("{}Signals", name);
+    let names: Vec<&Ident> = fields
+        .iter()
+        .map(|f| f.ident.as_ref().expect("Named fields have an ident"))
+        .collect();
+    let declarations = fields.iter().map(|f| {
+        let field_vis = &f.vis;
+        let field_name = &f.ident;
+        let field_type = &f.ty;
+        quote!(#field_vis #field_name: ::viste_reactive::ValueSignal<'a, #field_type>)
+    });
+    let signals: Vec<TokenStream> = names.iter().map(|n| quote!(self.#n)).collect();
+    let patterns: Vec<TokenStream> = names.iter().map(|n| quote!(#n)).collect();
+    let combined = combine(&signals, &patterns, quote!(#name { #(#names),* }));
+    Ok(quote! {
+        #vis struct #signals_name<'a> {
+            #(#declarations),*
+        }
+
+        impl<'a> #signals_name<'a> {
+            #vis fn signal(&self) -> ::viste_reactive::ValueSignal<'a, #name> {
+                #combined
+            }
+        }
+    })
+}
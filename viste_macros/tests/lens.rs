@@ -0,0 +1,70 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use viste_macros::Lens;
+use viste_reactive::*;
+
+#[derive(Clone, PartialEq, Debug, Lens)]
+pub struct Settings {
+    pub volume: u8,
+    pub muted: bool,
+}
+
+#[derive(Clone, PartialEq, Debug, Lens)]
+pub struct AppState {
+    pub title: String,
+    pub settings: Settings,
+}
+
+fn initial() -> AppState {
+    AppState {
+        title: "viste".to_owned(),
+        settings: Settings {
+            volume: 5,
+            muted: false,
+        },
+    }
+}
+
+#[test]
+fn test_lens_get_set() {
+    let lens = AppState::title_lens();
+    let mut state = initial();
+    assert_eq!((lens.get)(&state), "viste");
+    (lens.set)(&mut state, "changed".to_owned());
+    assert_eq!(state.title, "changed");
+}
+
+#[test]
+fn test_focus_lens() {
+    let world = World::new();
+    let (_set_state, state) = mutable(&world, initial());
+    let settings = state.focus_lens(AppState::settings_lens());
+    let volume = settings.focus_lens(Settings::volume_lens());
+    let title_runs = Rc::new(Cell::new(0));
+    let runs = title_runs.clone();
+    let title_length = state.focus_lens(AppState::title_lens()).map(move |t| {
+        runs.set(runs.get() + 1);
+        t.len()
+    });
+    assert_eq!(read_once(&title_length), 5);
+
+    volume.write(7);
+    assert_eq!(read_once(&state).settings.volume, 7);
+    assert_eq!(read_once(&volume), 7);
+    settings.write(Settings {
+        volume: 1,
+        muted: true,
+    });
+    assert_eq!(read_once(&volume), 1);
+    assert!(read_once(&state).settings.muted);
+    // Writes to other fields never rerun computations on the title
+    assert_eq!(read_once(&title_length), 5);
+    assert_eq!(title_runs.get(), 1);
+
+    state.write(AppState {
+        title: "reactive".to_owned(),
+        ..initial()
+    });
+    assert_eq!(read_once(&title_length), 8);
+    assert_eq!(read_once(&volume), 5);
+}
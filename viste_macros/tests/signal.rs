@@ -0,0 +1,67 @@
+use viste_macros::{signal, Signals};
+use viste_reactive::*;
+
+#[derive(Clone, PartialEq, Debug, Signals)]
+pub struct Rect {
+    pub width: u32,
+    pub height: u32,
+    pub label: String,
+}
+
+#[test]
+fn test_signal_expression() {
+    let world = World::new();
+    let (set_a, a) = mutable(&world, 1);
+    let (set_b, b) = mutable(&world, 2);
+    let (_set_c, c) = mutable(&world, 3);
+    let offset = 10;
+    let result = signal!($a + $b * $c + offset);
+    assert_eq!(read_once(&result), 17);
+    set_b(4);
+    assert_eq!(read_once(&result), 23);
+    set_a(0);
+    assert_eq!(read_once(&result), 22);
+}
+
+#[test]
+fn test_signal_repeated_and_nested() {
+    let world = World::new();
+    let (set_a, a) = mutable(&world, 3);
+    let squared = signal!($a * $a);
+    let (_set_b, b) = mutable(&world, 1);
+    let nested = signal!({
+        let sum = $squared + $b;
+        (sum, format!("{}", $a))
+    });
+    assert_eq!(read_once(&squared), 9);
+    assert_eq!(read_once(&nested), (10, "3".to_owned()));
+    set_a(4);
+    assert_eq!(read_once(&nested), (17, "4".to_owned()));
+}
+
+#[test]
+fn test_signals_derive() {
+    let world = World::new();
+    let (set_width, width) = mutable(&world, 2);
+    let (_set_height, height) = mutable(&world, 3);
+    let (set_label, label) = mutable(&world, "a".to_owned());
+    let signals = RectSignals {
+        width: width.into(),
+        height: height.into(),
+        label: label.into(),
+    };
+    let rect = signals.signal();
+    let area = signal!($rect.width * $rect.height);
+    assert_eq!(
+        read_once(&rect),
+        Rect {
+            width: 2,
+            height: 3,
+            label: "a".to_owned()
+        }
+    );
+    set_width(5);
+    set_label("b".to_owned());
+    assert_eq!(read_once(&area), 15);
+    assert_eq!(read_once(&rect).label, "b");
+}
@@ -16,6 +16,7 @@ pub fn simple_benchmark(c: &mut Criterion) {
     });
 }
 
+#[allow(clippy::needless_borrow)]
 fn generate_tree<'a>(
     world: &'a World,
     setters: &mut Vec<Box<dyn Fn(i32)>>,
@@ -25,7 +26,7 @@ fn generate_tree<'a>(
     if depth == max_depth {
         let (set, v) = mutable(&world, 0);
         setters.push(Box::new(set));
-        v
+        v.into()
     } else {
         let s1 = generate_tree(world, setters, depth + 1, max_depth);
         let s2 = generate_tree(world, setters, depth + 1, max_depth);
@@ -35,6 +36,7 @@ fn generate_tree<'a>(
     }
 }
 
+#[allow(unused_variables)]
 pub fn values_benchmark(c: &mut Criterion) {
     let world = World::new();
     let mut setters = Vec::new();
@@ -55,6 +57,7 @@ pub fn values_benchmark(c: &mut Criterion) {
     });
 }
 
+#[allow(unused_variables, unused_mut)]
 pub fn stream_benchmark(c: &mut Criterion) {
     c.bench_function("stream many", |b| {
         b.iter(|| {
@@ -85,10 +88,33 @@ pub fn stream_benchmark(c: &mut Criterion) {
     });
 }
 
+pub fn fan_out_benchmark(c: &mut Criterion) {
+    for readers in [1, 10, 100] {
+        c.bench_function(&format!("stream fan out {}", readers), |b| {
+            b.iter(|| {
+                let world = World::new();
+                let (send, s) = portal(&world);
+                let mut collectors: Vec<_> = (0..readers).map(|_| s.collect()).collect();
+
+                for _ in 0..10 {
+                    for i in 0..100 {
+                        send(black_box(i));
+                    }
+                    for collector in &mut collectors {
+                        collector.update();
+                        collector.clear();
+                    }
+                }
+            })
+        });
+    }
+}
+
 criterion_group!(
     benches,
     simple_benchmark,
     values_benchmark,
-    stream_benchmark
+    stream_benchmark,
+    fan_out_benchmark
 );
 criterion_main!(benches);
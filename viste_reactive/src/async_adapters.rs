@@ -0,0 +1,155 @@
+use crate::readers::{Reader, StreamReader};
+use crate::streams::portal::Portal;
+use crate::*;
+use futures::{Sink, Stream};
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pub struct AsyncStream<'a, T: Data + 'a> {
+    reader: StreamReader<'a, T>,
+    world: World,
+    node: NodeIndex,
+}
+
+impl<'a, T: Data + 'a> Stream for AsyncStream<'a, T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        match self.reader.read() {
+            StreamEvent::Item(t) => Poll::Ready(Some(t)),
+            StreamEvent::Complete => Poll::Ready(None),
+            StreamEvent::Pending => {
+                self.world.wake_on_dirty(self.node, cx.waker());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+// Sending never blocks, closing the sink completes the stream
+pub struct PortalSink<T: Data> {
+    portal: Rc<RefCell<Portal<T>>>,
+}
+
+impl<T: Data> Sink<T> for PortalSink<T> {
+    type Error = Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Infallible> {
+        self.portal.borrow_mut().send(item);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        self.portal.borrow_mut().complete();
+        Poll::Ready(Ok(()))
+    }
+}
+
+pub fn portal_sink<'a, T: Data + 'a>(world: &World) -> (PortalSink<T>, StreamSignal<'a, T>) {
+    let portal = Rc::new(RefCell::new(Portal::new(world.clone())));
+    let sink = PortalSink {
+        portal: portal.clone(),
+    };
+    (sink, StreamSignal(Signal(portal)))
+}
+
+impl<'a, T: Data + 'a> StreamSignal<'a, T> {
+    pub fn into_async_stream(self) -> AsyncStream<'a, T> {
+        let world = self.signal().world();
+        let node = self.signal().node();
+        AsyncStream {
+            reader: StreamReader::new(self.to_signal()),
+            world,
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> ValueSignal<'a, T> {
+    pub fn changes_stream(&self) -> AsyncStream<'a, T> {
+        self.changed().into_async_stream()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::{join, SinkExt, StreamExt};
+
+    #[test]
+    fn test_sink_to_async_stream() {
+        let world = World::new();
+        let (mut sink, stream) = portal_sink(&world);
+        let doubled = stream.map(|i: i32| i * 2).into_async_stream();
+        let (received, _) = block_on(async {
+            join!(doubled.collect::<Vec<_>>(), async {
+                for i in 1..4 {
+                    sink.send(i).await.unwrap();
+                }
+                sink.close().await.unwrap();
+            })
+        });
+        assert_eq!(received, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_async_stream_wakes_on_dirty() {
+        let world = World::new();
+        let (send, stream) = portal(&world);
+        let mut stream = stream.filter(|i: &i32| i % 2 == 0).into_async_stream();
+        let received = block_on(async {
+            let (first, _) = join!(stream.next(), async {
+                send(1);
+                send(2);
+            });
+            let (second, _) = join!(stream.next(), async { send(4) });
+            vec![first, second]
+        });
+        assert_eq!(received, vec![Some(2), Some(4)]);
+    }
+
+    #[test]
+    fn test_repeated_polls_keep_one_waker() {
+        let world = World::new();
+        let (send, stream) = portal(&world);
+        let mut stream = stream.into_async_stream();
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        for _ in 0..3 {
+            assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Pending);
+        }
+        assert_eq!(world.0.borrow().wakers.len(), 1);
+        send(1);
+        assert!(world.0.borrow().wakers.is_empty());
+        assert_eq!(
+            Pin::new(&mut stream).poll_next(&mut cx),
+            Poll::Ready(Some(1))
+        );
+    }
+
+    #[test]
+    fn test_changes_stream() {
+        let world = World::new();
+        let (set, value) = mutable(&world, 1);
+        let changes = value.map(|i| i * 10).changes_stream();
+        let received = block_on(async {
+            // Values only report their latest state, so the intermediate 20 is never seen
+            let (received, _) = join!(changes.take(2).collect::<Vec<_>>(), async {
+                set(2);
+                set(3);
+            });
+            received
+        });
+        assert_eq!(received, vec![10, 30]);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::readers::{Reader, StreamReader};
+use crate::streams::portal::Portal;
+use crate::*;
+use std::rc::Weak;
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+
+// Drains the receiver on every World::poll, the stream completes once all senders are gone
+pub fn portal_from_receiver<'a, T: Data + 'static>(
+    world: &World,
+    receiver: Receiver<T>,
+) -> StreamSignal<'a, T> {
+    let portal = Rc::new(RefCell::new(Portal::new(world.clone())));
+    let weak: Weak<RefCell<Portal<T>>> = Rc::downgrade(&portal);
+    world.register_input(Box::new(move || match weak.upgrade() {
+        Some(portal) => loop {
+            match receiver.try_recv() {
+                Ok(value) => portal.borrow_mut().send(value),
+                Err(TryRecvError::Empty) => break true,
+                Err(TryRecvError::Disconnected) => {
+                    portal.borrow_mut().complete();
+                    break false;
+                }
+            }
+        },
+        None => false,
+    }));
+    StreamSignal(Signal(portal))
+}
+
+struct ForwarderState<T: Data + 'static> {
+    reader: StreamReader<'static, T>,
+    sender: Option<Sender<T>>,
+}
+
+// Forwards items on every World::poll until it is dropped, the stream completes or the receiver hangs up
+pub struct Forwarder<T: Data + 'static> {
+    _state: Rc<RefCell<ForwarderState<T>>>,
+}
+
+impl<T: Data + 'static> StreamSignal<'static, T> {
+    pub fn forward_to(&self, sender: Sender<T>) -> Forwarder<T> {
+        let state = Rc::new(RefCell::new(ForwarderState {
+            reader: StreamReader::new(self.signal().clone()),
+            sender: Some(sender),
+        }));
+        let weak = Rc::downgrade(&state);
+        self.signal()
+            .world()
+            .register_output(Box::new(move || match weak.upgrade() {
+                Some(state) => state.borrow_mut().forward(),
+                None => false,
+            }));
+        Forwarder { _state: state }
+    }
+}
+
+impl<T: Data + 'static> ForwarderState<T> {
+    fn forward(&mut self) -> bool {
+        loop {
+            match self.reader.read() {
+                StreamEvent::Item(value) => match &self.sender {
+                    Some(sender) if sender.send(value).is_ok() => {}
+                    _ => {
+                        self.sender = None;
+                        return false;
+                    }
+                },
+                StreamEvent::Pending => return true,
+                StreamEvent::Complete => {
+                    self.sender = None;
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+    use std::thread;
+
+    #[test]
+    fn test_receiver_to_sender() {
+        let world = World::new();
+        let (worker_tx, worker_rx) = channel();
+        let (out_tx, out_rx) = channel();
+        let stream = portal_from_receiver(&world, worker_rx);
+        let _forwarder = stream.map(|i: i32| i * 2).forward_to(out_tx);
+        let worker = thread::spawn(move || {
+            for i in 1..4 {
+                worker_tx.send(i).unwrap();
+            }
+        });
+        worker.join().unwrap();
+        world.poll();
+        assert_eq!(out_rx.try_iter().collect::<Vec<_>>(), vec![2, 4, 6]);
+        // The worker hung up, so the stream completed and the forwarder released its sender
+        assert!(out_rx.recv().is_err());
+    }
+
+    #[test]
+    fn test_forwarder_drop() {
+        let world = World::new();
+        let (send, stream) = portal(&world);
+        let (tx, rx) = channel();
+        let forwarder = stream.forward_to(tx);
+        send(1);
+        world.poll();
+        assert_eq!(rx.try_recv(), Ok(1));
+        drop(forwarder);
+        send(2);
+        world.poll();
+        assert!(rx.recv().is_err());
+    }
+}
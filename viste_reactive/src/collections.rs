@@ -1,16 +1,196 @@
+use crate::streams::differ::Differ;
 use crate::streams::filter_mapper::FilterMapper;
+use crate::values::folder::Folder;
 use crate::*;
+use log::warn;
 use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::hash::Hash;
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SetChange<T: Data> {
     Added(T),
     Removed(T),
+    AddedBatch(Vec<T>),
+    RemovedBatch(Vec<T>),
+    Replaced(T, T),
     Clear,
 }
 
+impl<T: Data> SetChange<T> {
+    pub fn map<R: Data, M: Fn(T) -> R>(self, mapper: M) -> SetChange<R> {
+        match self {
+            SetChange::Added(t) => SetChange::Added(mapper(t)),
+            SetChange::Removed(t) => SetChange::Removed(mapper(t)),
+            SetChange::AddedBatch(ts) => {
+                SetChange::AddedBatch(ts.into_iter().map(mapper).collect())
+            }
+            SetChange::RemovedBatch(ts) => {
+                SetChange::RemovedBatch(ts.into_iter().map(mapper).collect())
+            }
+            SetChange::Replaced(old, new) => SetChange::Replaced(mapper(old), mapper(new)),
+            SetChange::Clear => SetChange::Clear,
+        }
+    }
+
+    pub fn filter_map<R: Data, F: Fn(T) -> Option<R>>(self, f: F) -> Option<SetChange<R>> {
+        match self {
+            SetChange::Added(t) => f(t).map(SetChange::Added),
+            SetChange::Removed(t) => f(t).map(SetChange::Removed),
+            SetChange::AddedBatch(ts) => {
+                SetChange::added_batch(ts.into_iter().filter_map(f).collect())
+            }
+            SetChange::RemovedBatch(ts) => {
+                SetChange::removed_batch(ts.into_iter().filter_map(f).collect())
+            }
+            SetChange::Replaced(old, new) => match (f(old), f(new)) {
+                (Some(old), Some(new)) => Some(SetChange::Replaced(old, new)),
+                (Some(old), None) => Some(SetChange::Removed(old)),
+                (None, Some(new)) => Some(SetChange::Added(new)),
+                (None, None) => None,
+            },
+            SetChange::Clear => Some(SetChange::Clear),
+        }
+    }
+
+    fn added_batch(ts: Vec<T>) -> Option<SetChange<T>> {
+        if ts.is_empty() {
+            None
+        } else {
+            Some(SetChange::AddedBatch(ts))
+        }
+    }
+
+    fn removed_batch(ts: Vec<T>) -> Option<SetChange<T>> {
+        if ts.is_empty() {
+            None
+        } else {
+            Some(SetChange::RemovedBatch(ts))
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum InconsistencyPolicy {
+    #[default]
+    Ignore,
+    Log,
+    Panic,
+    Collect,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inconsistency<T: Data> {
+    MissingRemoval(T),
+}
+
+impl<T: Data> Inconsistency<T> {
+    fn description(&self) -> &'static str {
+        match self {
+            Inconsistency::MissingRemoval(_) => {
+                "Tried to remove an element that is not in the view"
+            }
+        }
+    }
+}
+
+pub struct ConsistencyChecker<T: Data> {
+    policy: InconsistencyPolicy,
+    inconsistencies: Vec<Inconsistency<T>>,
+}
+
+impl<T: Data> ConsistencyChecker<T> {
+    pub fn new(policy: InconsistencyPolicy) -> Self {
+        Self {
+            policy,
+            inconsistencies: Vec::new(),
+        }
+    }
+
+    pub fn policy(&self) -> InconsistencyPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: InconsistencyPolicy) {
+        self.policy = policy;
+    }
+
+    pub fn report(&mut self, inconsistency: Inconsistency<T>) {
+        match self.policy {
+            InconsistencyPolicy::Ignore => (),
+            InconsistencyPolicy::Log => warn!("{}", inconsistency.description()),
+            InconsistencyPolicy::Panic => panic!("{}", inconsistency.description()),
+            InconsistencyPolicy::Collect => self.inconsistencies.push(inconsistency),
+        }
+    }
+
+    pub fn check_removed(&mut self, removed: bool, t: &T) {
+        if !removed {
+            self.report(Inconsistency::MissingRemoval(t.cheap_clone()))
+        }
+    }
+
+    pub fn inconsistencies(&self) -> &[Inconsistency<T>] {
+        &self.inconsistencies
+    }
+
+    pub fn take_inconsistencies(&mut self) -> Vec<Inconsistency<T>> {
+        std::mem::take(&mut self.inconsistencies)
+    }
+}
+
+impl<T: Data> Default for ConsistencyChecker<T> {
+    fn default() -> Self {
+        Self::new(InconsistencyPolicy::default())
+    }
+}
+
+fn diff_hash_sets<T: Data + Hash + Eq>(old: &HashSet<T>, new: &HashSet<T>) -> Vec<SetChange<T>> {
+    SetChange::removed_batch(old.difference(new).map(|t| t.cheap_clone()).collect())
+        .into_iter()
+        .chain(SetChange::added_batch(
+            new.difference(old).map(|t| t.cheap_clone()).collect(),
+        ))
+        .collect()
+}
+
+fn diff_vecs_by_key<T: Data, K: Hash + Eq, KF: Fn(&T) -> K>(
+    old: &[T],
+    new: &[T],
+    key_func: &KF,
+) -> Vec<SetChange<T>> {
+    let mut remaining: HashMap<K, VecDeque<&T>> = HashMap::new();
+    for t in old {
+        remaining.entry(key_func(t)).or_default().push_back(t);
+    }
+    let mut removed = Vec::new();
+    let mut replaced = Vec::new();
+    let mut added = Vec::new();
+    for t in new {
+        match remaining
+            .get_mut(&key_func(t))
+            .and_then(|olds| olds.pop_front())
+        {
+            Some(o) if !o.changed(t) => (),
+            Some(o) => replaced.push(SetChange::Replaced(o.cheap_clone(), t.cheap_clone())),
+            None => added.push(t.cheap_clone()),
+        }
+    }
+    removed.extend(
+        remaining
+            .into_values()
+            .flat_map(|olds| olds.into_iter())
+            .map(|o| o.cheap_clone()),
+    );
+    // All removals go before additions, so keyed views never drop a freshly added row
+    SetChange::removed_batch(removed)
+        .into_iter()
+        .chain(replaced)
+        .chain(SetChange::added_batch(added))
+        .collect()
+}
+
 pub trait View<'a, T: Data + 'a> {
     type Item;
     fn update(&mut self);
@@ -58,13 +238,13 @@ impl<Item: Data> StateItems<Item> {
 }
 
 pub struct CollectionComputationCore<'a, T: Data + 'a, D: DirectView<'a, T> + 'a> {
-    stream_signal: Signal<'a, Option<SetChange<T>>>,
+    stream_signal: Signal<'a, StreamEvent<SetChange<T>>>,
     view: D,
     state_items: StateItems<SetChange<T>>,
 }
 
 impl<'a, T: Data + 'a, D: DirectView<'a, T> + 'a> CollectionComputationCore<'a, T, D> {
-    pub fn new(signal: Signal<'a, Option<SetChange<T>>>) -> Self {
+    pub fn new(signal: Signal<'a, StreamEvent<SetChange<T>>>) -> Self {
         Self {
             stream_signal: signal.clone(),
             view: D::new(signal.collect()),
@@ -77,24 +257,40 @@ impl<'a, T: Data + 'a, D: DirectView<'a, T> + 'a> CollectionComputationCore<'a,
     }
 }
 
+trait ViewItems<T> {
+    fn find_items(&mut self, predicate: &dyn Fn(&T) -> bool) -> Vec<T>;
+}
+
+impl<'a, T: Data + 'a, D: DirectView<'a, T> + 'a> ViewItems<T>
+    for CollectionComputationCore<'a, T, D>
+{
+    fn find_items(&mut self, predicate: &dyn Fn(&T) -> bool) -> Vec<T> {
+        self.iter_view_items()
+            .filter(|t| predicate(t))
+            .map(|t| t.cheap_clone())
+            .collect()
+    }
+}
+
 impl<'a, T: Data + 'a, D: DirectView<'a, T> + 'a> ComputationCore
     for CollectionComputationCore<'a, T, D>
 {
-    type ComputationResult = Option<SetChange<T>>;
+    type ComputationResult = StreamEvent<SetChange<T>>;
 
     fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
-        self.state_items
-            .get_next(reader)
-            .or_else(|| self.stream_signal.compute(reader))
+        match self.state_items.get_next(reader) {
+            Some(change) => StreamEvent::Item(change),
+            None => self.stream_signal.compute(reader),
+        }
     }
 
     fn create_reader(&mut self) -> ReaderToken {
+        // The view has to be brought up to date before the reader exists,
+        // otherwise pending changes would be delivered twice
+        let items = self.iter_view_items().map(|t| t.cheap_clone()).collect();
         let r = self.stream_signal.create_reader();
-        let items = self
-            .iter_view_items()
-            .map(|t| SetChange::Added(t.cheap_clone()))
-            .collect();
-        self.state_items.insert(r, items);
+        self.state_items
+            .insert(r, SetChange::added_batch(items).into_iter().collect());
         r
     }
 
@@ -124,7 +320,7 @@ impl<'a, T: Data + 'a, D: DirectView<'a, T> + 'a> ComputationCore
     }
 }
 
-pub struct CollectionSignal<'a, T: Data + 'a>(Signal<'a, Option<SetChange<T>>>);
+pub struct CollectionSignal<'a, T: Data + 'a>(Signal<'a, StreamEvent<SetChange<T>>>);
 
 impl<'a, T: Data + 'a> CollectionSignal<'a, T> {
     pub fn new<D: DirectView<'a, T> + 'a>(signal: StreamSignal<'a, SetChange<T>>) -> Self {
@@ -132,7 +328,7 @@ impl<'a, T: Data + 'a> CollectionSignal<'a, T> {
         Self(Signal::create(core))
     }
 
-    pub fn create<C: ComputationCore<ComputationResult = Option<SetChange<T>>> + 'a>(
+    pub fn create<C: ComputationCore<ComputationResult = StreamEvent<SetChange<T>>> + 'a>(
         core: C,
     ) -> Self {
         CollectionSignal(Signal::create(
@@ -140,39 +336,29 @@ impl<'a, T: Data + 'a> CollectionSignal<'a, T> {
         ))
     }
 
-    pub fn signal(&self) -> &Signal<'a, Option<SetChange<T>>> {
+    pub fn signal(&self) -> &Signal<'a, StreamEvent<SetChange<T>>> {
         &self.0
     }
 
-    pub fn to_signal(self) -> Signal<'a, Option<SetChange<T>>> {
+    pub fn to_signal(self) -> Signal<'a, StreamEvent<SetChange<T>>> {
         self.0
     }
 
     pub fn map<R: Data + 'a, M: Fn(T) -> R + 'a>(&self, mapper: M) -> CollectionSignal<'a, R> {
         CollectionSignal(Signal::create(
             CollectionComputationCore::<R, VecView<R>>::new(Signal::create(
-                streams::mapper::Mapper::new(
-                    self.signal().world(),
-                    self.0.clone(),
-                    move |c| match c {
-                        SetChange::Added(t) => SetChange::Added(mapper(t)),
-                        SetChange::Removed(t) => SetChange::Removed(mapper(t)),
-                        SetChange::Clear => SetChange::Clear,
-                    },
-                ),
+                streams::mapper::Mapper::new(self.signal().world(), self.0.clone(), move |c| {
+                    c.map(&mapper)
+                }),
             )),
         ))
     }
 
     pub fn filter<F: Fn(&T) -> bool + 'a>(&self, filter: F) -> CollectionSignal<'a, T> {
-        CollectionSignal::create(streams::filter::Filter::new(
+        CollectionSignal::create(FilterMapper::new(
             self.signal().world(),
             self.0.clone(),
-            move |c| match c {
-                SetChange::Added(t) => filter(t),
-                SetChange::Removed(t) => filter(t),
-                SetChange::Clear => true,
-            },
+            move |c| c.filter_map(|t| if filter(&t) { Some(t) } else { None }),
         ))
     }
 
@@ -183,11 +369,7 @@ impl<'a, T: Data + 'a> CollectionSignal<'a, T> {
         CollectionSignal::create(FilterMapper::new(
             self.signal().world(),
             self.0.clone(),
-            move |c| match c {
-                SetChange::Added(t) => f(t).map(SetChange::Added),
-                SetChange::Removed(t) => f(t).map(SetChange::Removed),
-                SetChange::Clear => Some(SetChange::Clear),
-            },
+            move |c| c.filter_map(&f),
         ))
     }
 
@@ -240,25 +422,81 @@ impl<'a, T: Data + 'a> CollectionSignal<'a, T> {
         OrderedVecView::new(self.collect(), key_func)
     }
 
+    pub fn view_incremental<S: IncrementalState<T>>(&self, state: S) -> IncrementalView<'a, T, S> {
+        IncrementalView::from_state(self.collect(), state)
+    }
+
     pub fn view_vec(&self) -> VecView<'a, T>
     where
         T: PartialEq,
     {
         VecView::new(self.collect())
     }
+
+    pub fn validator(&self) -> Validator<'a, T> {
+        Validator::new(self.collect())
+    }
+
+    pub fn snapshot(&self) -> ValueSignal<'a, Vec<T>> {
+        ValueSignal::create(Folder::new(
+            self.signal().world(),
+            StreamSignal::new(self.0.clone()),
+            Vec::new(),
+            |mut store, change| {
+                apply_change(&mut store, &mut ConsistencyChecker::default(), change);
+                store
+            },
+        ))
+    }
+}
+
+impl<'a, T: Data + Hash + Eq + 'a> ValueSignal<'a, HashSet<T>> {
+    pub fn diff_to_collection(&self) -> CollectionSignal<'a, T> {
+        CollectionSignal::create(Differ::new(
+            self.signal().world(),
+            self.clone(),
+            HashSet::new(),
+            diff_hash_sets,
+        ))
+    }
+}
+
+impl<'a, T: Data + 'a> ValueSignal<'a, Vec<T>> {
+    pub fn diff_to_collection(&self) -> CollectionSignal<'a, T>
+    where
+        T: Hash + Eq,
+    {
+        self.diff_to_collection_by_key(|t| t.cheap_clone())
+    }
+
+    pub fn diff_to_collection_by_key<K: Hash + Eq + 'a, KF: Fn(&T) -> K + 'a>(
+        &self,
+        key_func: KF,
+    ) -> CollectionSignal<'a, T> {
+        CollectionSignal::create(Differ::new(
+            self.signal().world(),
+            self.clone(),
+            Vec::new(),
+            move |old: &Vec<T>, new: &Vec<T>| diff_vecs_by_key(old, new, &key_func),
+        ))
+    }
 }
 
 pub struct CollectionPortal<'a, T: Data + 'a> {
     signal: CollectionSignal<'a, T>,
+    items: Rc<RefCell<dyn ViewItems<T> + 'a>>,
     sender: Box<dyn Fn(SetChange<T>) + 'a>,
 }
 
 impl<'a, T: Data + 'a> CollectionPortal<'a, T> {
     pub fn new<D: DirectView<'a, T> + 'a>(world: &World) -> Self {
         let (sender, signal) = portal(world);
+        let core: Rc<RefCell<CollectionComputationCore<T, D>>> =
+            Rc::new(RefCell::new(CollectionComputationCore::new(signal.0)));
         CollectionPortal {
             sender: Box::new(sender),
-            signal: CollectionSignal::new::<D>(signal),
+            signal: CollectionSignal(Signal(core.clone())),
+            items: core,
         }
     }
 
@@ -266,6 +504,10 @@ impl<'a, T: Data + 'a> CollectionPortal<'a, T> {
         &self.signal
     }
 
+    pub fn apply(&mut self, change: SetChange<T>) {
+        (self.sender)(change)
+    }
+
     pub fn add(&mut self, t: T) {
         (self.sender)(SetChange::Added(t))
     }
@@ -274,181 +516,333 @@ impl<'a, T: Data + 'a> CollectionPortal<'a, T> {
         (self.sender)(SetChange::Removed(t))
     }
 
+    pub fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        if let Some(change) = SetChange::added_batch(items.into_iter().collect()) {
+            (self.sender)(change)
+        }
+    }
+
+    pub fn remove_all<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        if let Some(change) = SetChange::removed_batch(items.into_iter().collect()) {
+            (self.sender)(change)
+        }
+    }
+
+    pub fn replace(&mut self, old: T, new: T) {
+        (self.sender)(SetChange::Replaced(old, new))
+    }
+
+    pub fn update_where<P: Fn(&T) -> bool, F: Fn(&T) -> T>(&mut self, predicate: P, f: F) {
+        let matching = self.items.borrow_mut().find_items(&predicate);
+        for old in matching {
+            let new = f(&old);
+            if new.changed(&old) {
+                self.replace(old, new)
+            }
+        }
+    }
+
     pub fn clear(&mut self) {
         (self.sender)(SetChange::Clear)
     }
 }
 
-pub struct HashSetView<'a, T: Data + Hash + Eq + 'a> {
+pub trait IncrementalState<T: Data> {
+    fn on_add(&mut self, t: T);
+    // Returns whether t was actually part of the state
+    fn on_remove(&mut self, t: &T) -> bool;
+    fn on_clear(&mut self);
+
+    fn on_add_batch(&mut self, ts: Vec<T>) {
+        ts.into_iter().for_each(|t| self.on_add(t))
+    }
+
+    // Returns the elements that were not part of the state
+    fn on_remove_batch(&mut self, ts: Vec<T>) -> Vec<T> {
+        ts.into_iter().filter(|t| !self.on_remove(t)).collect()
+    }
+
+    fn on_replace(&mut self, old: &T, new: T) -> bool {
+        let removed = self.on_remove(old);
+        self.on_add(new);
+        removed
+    }
+}
+
+pub trait IterableState {
+    type Item;
+    fn iter_items<'b>(&'b self) -> Box<dyn Iterator<Item = &'b Self::Item> + 'b>;
+}
+
+fn apply_change<T: Data, S: IncrementalState<T>>(
+    state: &mut S,
+    checker: &mut ConsistencyChecker<T>,
+    change: SetChange<T>,
+) {
+    match change {
+        SetChange::Added(t) => state.on_add(t),
+        SetChange::Removed(t) => checker.check_removed(state.on_remove(&t), &t),
+        SetChange::AddedBatch(ts) => state.on_add_batch(ts),
+        SetChange::RemovedBatch(ts) => state
+            .on_remove_batch(ts)
+            .into_iter()
+            .for_each(|t| checker.report(Inconsistency::MissingRemoval(t))),
+        SetChange::Replaced(old, new) => checker.check_removed(state.on_replace(&old, new), &old),
+        SetChange::Clear => state.on_clear(),
+    }
+}
+
+pub struct IncrementalView<'a, T: Data + 'a, S: IncrementalState<T>> {
     collector: Collector<'a, SetChange<T>>,
-    data: HashSet<T>,
+    checker: ConsistencyChecker<T>,
+    state: S,
 }
 
-impl<'a, T: Data + Hash + Eq + 'a> View<'a, T> for HashSetView<'a, T> {
-    type Item = T;
+impl<'a, T: Data + 'a, S: IncrementalState<T>> IncrementalView<'a, T, S> {
+    pub fn from_state(collector: Collector<'a, SetChange<T>>, state: S) -> Self {
+        Self {
+            collector,
+            checker: ConsistencyChecker::default(),
+            state,
+        }
+    }
 
-    fn update(&mut self) {
-        self.collector.update();
-        let store = &mut self.data;
+    pub fn with_policy(mut self, policy: InconsistencyPolicy) -> Self {
+        self.checker.set_policy(policy);
+        self
+    }
 
+    pub fn inconsistencies(&self) -> &[Inconsistency<T>] {
+        self.checker.inconsistencies()
+    }
+
+    pub fn update(&mut self) {
+        self.collector.update();
+        let state = &mut self.state;
+        let checker = &mut self.checker;
         self.collector
-            .items
-            .drain(..)
-            .for_each(|change| match change {
-                SetChange::Added(t) => {
-                    store.insert(t);
-                }
-                SetChange::Removed(t) => {
-                    store.remove(&t);
-                }
-                SetChange::Clear => store.clear(),
-            });
-        self.collector.clear();
+            .drain()
+            .for_each(|change| apply_change(state, checker, change));
     }
 
-    fn iter_unchanged<'b>(&'b self) -> Box<dyn Iterator<Item = &Self::Item> + 'b> {
-        Box::new(self.data.iter())
+    pub fn state_unchanged(&self) -> &S {
+        &self.state
+    }
+
+    pub fn state(&mut self) -> &S {
+        self.update();
+        self.state_unchanged()
+    }
+}
+
+impl<'a, T: Data + 'a, S: IncrementalState<T> + IterableState> View<'a, T>
+    for IncrementalView<'a, T, S>
+{
+    type Item = S::Item;
+
+    fn update(&mut self) {
+        IncrementalView::update(self)
+    }
+
+    fn iter_unchanged<'b>(&'b self) -> Box<dyn Iterator<Item = &'b Self::Item> + 'b> {
+        self.state.iter_items()
     }
 }
 
-impl<'a, T: Data + Hash + Eq + 'a> DirectView<'a, T> for HashSetView<'a, T> {
+impl<'a, T: Data + 'a, S: IncrementalState<T> + IterableState<Item = T> + Default> DirectView<'a, T>
+    for IncrementalView<'a, T, S>
+{
     fn new(collector: Collector<'a, SetChange<T>>) -> Self {
-        Self {
-            data: HashSet::new(),
-            collector,
-        }
+        Self::from_state(collector, S::default())
     }
 }
 
+impl<T: Data + Hash + Eq> IncrementalState<T> for HashSet<T> {
+    fn on_add(&mut self, t: T) {
+        self.insert(t);
+    }
+
+    fn on_remove(&mut self, t: &T) -> bool {
+        self.remove(t)
+    }
+
+    fn on_clear(&mut self) {
+        self.clear()
+    }
+
+    fn on_add_batch(&mut self, ts: Vec<T>) {
+        self.extend(ts)
+    }
+}
+
+impl<T: Data + Hash + Eq> IterableState for HashSet<T> {
+    type Item = T;
+
+    fn iter_items<'b>(&'b self) -> Box<dyn Iterator<Item = &'b T> + 'b> {
+        Box::new(self.iter())
+    }
+}
+
+pub type HashSetView<'a, T> = IncrementalView<'a, T, HashSet<T>>;
+
 impl<'a, T: Data + Hash + Eq + 'a> HashSetView<'a, T> {
+    pub fn new(collector: Collector<'a, SetChange<T>>) -> Self {
+        Self::from_state(collector, HashSet::new())
+    }
+
     pub fn data_unchanged(&self) -> &HashSet<T> {
-        &self.data
+        self.state_unchanged()
     }
 
     pub fn data(&mut self) -> &HashSet<T> {
-        self.update();
-        self.data_unchanged()
+        self.state()
     }
 }
 
-pub struct BTreeSetView<'a, T: Data + Eq + Ord + 'a> {
-    collector: Collector<'a, SetChange<T>>,
-    data: BTreeSet<T>,
+impl<T: Data + Ord> IncrementalState<T> for BTreeSet<T> {
+    fn on_add(&mut self, t: T) {
+        self.insert(t);
+    }
+
+    fn on_remove(&mut self, t: &T) -> bool {
+        self.remove(t)
+    }
+
+    fn on_clear(&mut self) {
+        self.clear()
+    }
+
+    fn on_add_batch(&mut self, ts: Vec<T>) {
+        self.extend(ts)
+    }
 }
 
+impl<T: Data + Ord> IterableState for BTreeSet<T> {
+    type Item = T;
+
+    fn iter_items<'b>(&'b self) -> Box<dyn Iterator<Item = &'b T> + 'b> {
+        Box::new(self.iter())
+    }
+}
+
+pub type BTreeSetView<'a, T> = IncrementalView<'a, T, BTreeSet<T>>;
+
 impl<'a, T: Data + Eq + Ord + 'a> BTreeSetView<'a, T> {
+    pub fn new(collector: Collector<'a, SetChange<T>>) -> Self {
+        Self::from_state(collector, BTreeSet::new())
+    }
+
     pub fn data_unchanged(&self) -> &BTreeSet<T> {
-        &self.data
+        self.state_unchanged()
     }
 
     pub fn data(&mut self) -> &BTreeSet<T> {
-        self.update();
-        self.data_unchanged()
+        self.state()
     }
 }
 
-impl<'a, T: Data + Eq + Ord + 'a> View<'a, T> for BTreeSetView<'a, T> {
-    type Item = T;
+pub trait MapStore<K, V> {
+    fn insert_value(&mut self, key: K, value: V);
+    fn remove_value(&mut self, key: &K) -> bool;
+    fn clear_values(&mut self);
+    fn iter_values<'b>(&'b self) -> Box<dyn Iterator<Item = &'b V> + 'b>;
+}
 
-    fn update(&mut self) {
-        self.collector.update();
-        let store = &mut self.data;
+impl<K: Hash + Eq, V> MapStore<K, V> for HashMap<K, V> {
+    fn insert_value(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
 
-        self.collector
-            .items
-            .drain(..)
-            .for_each(|change| match change {
-                SetChange::Added(t) => {
-                    store.insert(t);
-                }
-                SetChange::Removed(t) => {
-                    store.remove(&t);
-                }
-                SetChange::Clear => store.clear(),
-            });
-        self.collector.clear();
+    fn remove_value(&mut self, key: &K) -> bool {
+        self.remove(key).is_some()
     }
 
-    fn iter_unchanged<'b>(&'b self) -> Box<dyn Iterator<Item = &Self::Item> + 'b> {
-        Box::new(self.data.iter())
+    fn clear_values(&mut self) {
+        self.clear()
+    }
+
+    fn iter_values<'b>(&'b self) -> Box<dyn Iterator<Item = &'b V> + 'b> {
+        Box::new(self.values())
     }
 }
 
-impl<'a, T: Data + Eq + Ord + 'a> DirectView<'a, T> for BTreeSetView<'a, T> {
-    fn new(collector: Collector<'a, SetChange<T>>) -> Self {
-        Self {
-            collector,
-            data: BTreeSet::new(),
-        }
+impl<K: Ord, V> MapStore<K, V> for BTreeMap<K, V> {
+    fn insert_value(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+
+    fn remove_value(&mut self, key: &K) -> bool {
+        self.remove(key).is_some()
+    }
+
+    fn clear_values(&mut self) {
+        self.clear()
+    }
+
+    fn iter_values<'b>(&'b self) -> Box<dyn Iterator<Item = &'b V> + 'b> {
+        Box::new(self.values())
     }
 }
 
-pub struct HashMapView<'a, T: Data + 'a, K: Hash + Eq + 'a, V: 'a> {
-    collector: Collector<'a, SetChange<T>>,
+pub struct MapState<'a, T: Data + 'a, K: 'a, V: 'a, M: MapStore<K, V>> {
     key_func: Box<dyn Fn(&T) -> K + 'a>,
     value_func: Box<dyn Fn(T) -> V + 'a>,
-    data: HashMap<K, V>,
+    data: M,
+}
+
+impl<'a, T: Data + 'a, K: 'a, V: 'a, M: MapStore<K, V>> IncrementalState<T>
+    for MapState<'a, T, K, V, M>
+{
+    fn on_add(&mut self, t: T) {
+        self.data
+            .insert_value((self.key_func)(&t), (self.value_func)(t))
+    }
+
+    fn on_remove(&mut self, t: &T) -> bool {
+        self.data.remove_value(&(self.key_func)(t))
+    }
+
+    fn on_clear(&mut self) {
+        self.data.clear_values()
+    }
 }
 
+impl<'a, T: Data + 'a, K: 'a, V: 'a, M: MapStore<K, V>> IterableState for MapState<'a, T, K, V, M> {
+    type Item = V;
+
+    fn iter_items<'b>(&'b self) -> Box<dyn Iterator<Item = &'b V> + 'b> {
+        self.data.iter_values()
+    }
+}
+
+pub type HashMapView<'a, T, K, V> = IncrementalView<'a, T, MapState<'a, T, K, V, HashMap<K, V>>>;
+
 impl<'a, T: Data + 'a, K: Hash + Eq + 'a, V: 'a> HashMapView<'a, T, K, V> {
     pub fn new<KF: Fn(&T) -> K + 'a, VF: Fn(T) -> V + 'a>(
         collector: Collector<'a, SetChange<T>>,
         key_func: KF,
         value_func: VF,
     ) -> Self {
-        Self {
+        Self::from_state(
             collector,
-            data: HashMap::new(),
-            key_func: Box::new(key_func),
-            value_func: Box::new(value_func),
-        }
+            MapState {
+                key_func: Box::new(key_func),
+                value_func: Box::new(value_func),
+                data: HashMap::new(),
+            },
+        )
     }
 
     pub fn unchanged_data(&self) -> &HashMap<K, V> {
-        &self.data
+        &self.state_unchanged().data
     }
 
     pub fn data(&mut self) -> &HashMap<K, V> {
-        self.update();
-        self.unchanged_data()
+        &self.state().data
     }
 }
 
-impl<'a, T: Data + 'a, K: Hash + Eq + 'a, V: 'a> View<'a, T> for HashMapView<'a, T, K, V> {
-    type Item = V;
-
-    fn update(&mut self) {
-        self.collector.update();
-        let store = &mut self.data;
-        let kf = &self.key_func;
-        let vf = &self.value_func;
-
-        self.collector
-            .items
-            .drain(..)
-            .for_each(|change| match change {
-                SetChange::Added(t) => {
-                    store.insert((kf)(&t), (vf)(t));
-                }
-                SetChange::Removed(t) => {
-                    store.remove(&(kf)(&t));
-                }
-                SetChange::Clear => store.clear(),
-            });
-        self.collector.clear();
-    }
-
-    fn iter_unchanged<'b>(&'b self) -> Box<dyn Iterator<Item = &Self::Item> + 'b> {
-        Box::new(self.data.values())
-    }
-}
-
-pub struct BTreeMapView<'a, T: Data + 'a, K: Ord + Eq + 'a, V: 'a> {
-    collector: Collector<'a, SetChange<T>>,
-    key_func: Box<dyn Fn(&T) -> K + 'a>,
-    value_func: Box<dyn Fn(T) -> V + 'a>,
-    data: BTreeMap<K, V>,
-}
+pub type BTreeMapView<'a, T, K, V> = IncrementalView<'a, T, MapState<'a, T, K, V, BTreeMap<K, V>>>;
 
 impl<'a, T: Data + 'a, K: Ord + Eq + 'a, V: 'a> BTreeMapView<'a, T, K, V> {
     pub fn new<KF: Fn(&T) -> K + 'a, VF: Fn(T) -> V + 'a>(
@@ -456,227 +850,365 @@ impl<'a, T: Data + 'a, K: Ord + Eq + 'a, V: 'a> BTreeMapView<'a, T, K, V> {
         key_func: KF,
         value_func: VF,
     ) -> Self {
-        Self {
+        Self::from_state(
             collector,
-            data: BTreeMap::new(),
-            key_func: Box::new(key_func),
-            value_func: Box::new(value_func),
-        }
+            MapState {
+                key_func: Box::new(key_func),
+                value_func: Box::new(value_func),
+                data: BTreeMap::new(),
+            },
+        )
     }
 
     pub fn unchanged_data(&self) -> &BTreeMap<K, V> {
-        &self.data
+        &self.state_unchanged().data
     }
 
     pub fn data(&mut self) -> &BTreeMap<K, V> {
-        self.update();
-        self.unchanged_data()
+        &self.state().data
     }
 }
 
-impl<'a, T: Data + 'a, K: Ord + Eq + 'a, V: 'a> View<'a, T> for BTreeMapView<'a, T, K, V> {
-    type Item = V;
+pub struct IndexState<'a, T: Data + 'a, R: 'a> {
+    index_func: Box<dyn Fn(&T) -> usize + 'a>,
+    value_func: Box<dyn Fn(T) -> R + 'a>,
+    data: Vec<Option<R>>,
+}
 
-    fn update(&mut self) {
-        self.collector.update();
-        let store = &mut self.data;
-        let kf = &self.key_func;
-        let vf = &self.value_func;
+impl<'a, T: Data + 'a, R: 'a> IncrementalState<T> for IndexState<'a, T, R> {
+    fn on_add(&mut self, t: T) {
+        let idx: usize = (self.index_func)(&t);
+        if self.data.len() <= idx {
+            self.data.resize_with(idx + 1, || None);
+        }
+        self.data[idx] = Some((self.value_func)(t));
+    }
 
-        self.collector
-            .items
-            .drain(..)
-            .for_each(|change| match change {
-                SetChange::Added(t) => {
-                    store.insert((kf)(&t), (vf)(t));
-                }
-                SetChange::Removed(t) => {
-                    store.remove(&(kf)(&t));
-                }
-                SetChange::Clear => store.clear(),
-            });
-        self.collector.clear();
+    fn on_remove(&mut self, t: &T) -> bool {
+        let idx = (self.index_func)(t);
+        self.data.get_mut(idx).and_then(Option::take).is_some()
     }
 
-    fn iter_unchanged<'b>(&'b self) -> Box<dyn Iterator<Item = &Self::Item> + 'b> {
-        Box::new(self.data.values())
+    fn on_clear(&mut self) {
+        self.data.clear()
     }
 }
 
-pub struct VecIndexView<'a, T: Data + 'a, R: 'a> {
-    collector: Collector<'a, SetChange<T>>,
-    index_func: Box<dyn Fn(&T) -> usize + 'a>,
-    value_func: Box<dyn Fn(T) -> R + 'a>,
-    data: Vec<Option<R>>,
+impl<'a, T: Data + 'a, R: 'a> IterableState for IndexState<'a, T, R> {
+    type Item = Option<R>;
+
+    fn iter_items<'b>(&'b self) -> Box<dyn Iterator<Item = &'b Option<R>> + 'b> {
+        Box::new(self.data.iter())
+    }
 }
 
+pub type VecIndexView<'a, T, R> = IncrementalView<'a, T, IndexState<'a, T, R>>;
+
 impl<'a, T: Data + 'a, R: 'a> VecIndexView<'a, T, R> {
     pub fn new<IF: Fn(&T) -> usize + 'a, VF: Fn(T) -> R + 'a>(
         collector: Collector<'a, SetChange<T>>,
         index_func: IF,
         value_func: VF,
     ) -> Self {
-        Self {
+        Self::from_state(
             collector,
-            data: Vec::new(),
-            index_func: Box::new(index_func),
-            value_func: Box::new(value_func),
-        }
+            IndexState {
+                index_func: Box::new(index_func),
+                value_func: Box::new(value_func),
+                data: Vec::new(),
+            },
+        )
     }
 
     pub fn unchanged_data(&self) -> &Vec<Option<R>> {
-        &self.data
+        &self.state_unchanged().data
     }
 
     pub fn data(&mut self) -> &Vec<Option<R>> {
-        self.update();
-        self.unchanged_data()
+        &self.state().data
     }
 }
 
-impl<'a, T: Data + 'a, R: 'a> View<'a, T> for VecIndexView<'a, T, R> {
-    type Item = Option<R>;
+impl<T: Data> IncrementalState<T> for Vec<T> {
+    fn on_add(&mut self, t: T) {
+        self.push(t)
+    }
 
-    fn update(&mut self) {
-        self.collector.update();
-        let store = &mut self.data;
-        let idxf = &self.index_func;
-        let vf = &self.value_func;
+    fn on_remove(&mut self, t: &T) -> bool {
+        match self.iter().position(|x| x == t) {
+            Some(idx) => {
+                self.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
 
-        self.collector
-            .items
-            .drain(..)
-            .for_each(|change| match change {
-                SetChange::Added(t) => {
-                    let idx: usize = idxf(&t);
-                    if store.len() <= idx {
-                        store.resize_with(idx + 1, || None);
-                    }
-                    store[idx] = Some(vf(t));
-                }
-                SetChange::Removed(t) => {
-                    store[idxf(&t)] = None;
-                }
-                SetChange::Clear => store.clear(),
-            });
-        self.collector.clear();
+    fn on_clear(&mut self) {
+        self.clear()
     }
 
-    fn iter_unchanged<'b>(&'b self) -> Box<dyn Iterator<Item = &Self::Item> + 'b> {
-        Box::new(self.data.iter())
+    fn on_add_batch(&mut self, ts: Vec<T>) {
+        self.extend(ts)
+    }
+
+    fn on_replace(&mut self, old: &T, new: T) -> bool {
+        match self.iter().position(|x| x == old) {
+            Some(idx) => {
+                self[idx] = new;
+                true
+            }
+            None => {
+                self.push(new);
+                false
+            }
+        }
     }
 }
 
-pub struct VecView<'a, T: Data + PartialEq + 'a> {
-    data: Vec<T>,
-    collector: Collector<'a, SetChange<T>>,
+impl<T: Data> IterableState for Vec<T> {
+    type Item = T;
+
+    fn iter_items<'b>(&'b self) -> Box<dyn Iterator<Item = &'b T> + 'b> {
+        Box::new(self.iter())
+    }
 }
 
-impl<'a, T: Data + PartialEq + 'a> VecView<'a, T> {
+pub type VecView<'a, T> = IncrementalView<'a, T, Vec<T>>;
+
+impl<'a, T: Data + 'a> VecView<'a, T> {
+    pub fn new(collector: Collector<'a, SetChange<T>>) -> Self {
+        Self::from_state(collector, Vec::new())
+    }
+
     pub fn unchanged_data(&self) -> &Vec<T> {
-        &self.data
+        self.state_unchanged()
     }
 
     pub fn data(&mut self) -> &Vec<T> {
-        self.update();
-        self.unchanged_data()
+        self.state()
     }
 }
 
-impl<'a, T: Data + PartialEq + 'a> View<'a, T> for VecView<'a, T> {
-    type Item = T;
+pub struct OrderedState<'a, T: Data + 'a, K: Copy + Eq + Ord + 'a> {
+    key_func: Box<dyn Fn(&T) -> K + 'a>,
+    data: Vec<(K, T)>,
+}
 
-    fn update(&mut self) {
-        self.collector.update();
-        let store = &mut self.data;
-        self.collector
-            .items
-            .drain(..)
-            .for_each(|change| match change {
-                SetChange::Added(t) => store.push(t),
-                SetChange::Clear => store.clear(),
-                SetChange::Removed(t) => {
-                    if let Some(idx) = store.iter().position(|x| x == &t) {
-                        store.remove(idx);
-                    }
-                }
-            })
+impl<'a, T: Data + 'a, K: Copy + Eq + Ord + 'a> OrderedState<'a, T, K> {
+    fn insert_keyed(&mut self, key: K, t: T) {
+        match self.data.binary_search_by_key(&key, |(k, _)| *k) {
+            Ok(existing_idx) => self.data[existing_idx] = (key, t),
+            Err(new_idx) => self.data.insert(new_idx, (key, t)),
+        }
     }
 
-    fn iter_unchanged<'b>(&'b self) -> Box<dyn Iterator<Item = &Self::Item> + 'b> {
-        Box::new(self.data.iter())
+    fn remove_keyed(&mut self, key: K) -> bool {
+        match self.data.binary_search_by_key(&key, |(k, _)| *k) {
+            Ok(existing_idx) => {
+                self.data.remove(existing_idx);
+                true
+            }
+            Err(_) => false,
+        }
     }
 }
 
-impl<'a, T: Data + PartialEq + 'a> DirectView<'a, T> for VecView<'a, T> {
-    fn new(collector: Collector<'a, SetChange<T>>) -> Self {
-        Self {
-            collector,
-            data: Vec::new(),
-        }
+impl<'a, T: Data + 'a, K: Copy + Eq + Ord + 'a> IncrementalState<T> for OrderedState<'a, T, K> {
+    fn on_add(&mut self, t: T) {
+        self.insert_keyed((self.key_func)(&t), t)
+    }
+
+    fn on_remove(&mut self, t: &T) -> bool {
+        self.remove_keyed((self.key_func)(t))
+    }
+
+    fn on_clear(&mut self) {
+        self.data.clear()
+    }
+
+    fn on_add_batch(&mut self, ts: Vec<T>) {
+        let keyf = &self.key_func;
+        self.data.extend(ts.into_iter().map(|t| (keyf(&t), t)));
+        // Stable sort keeps insertion order within a key, so the latest row wins the dedup
+        self.data.sort_by_key(|(k, _)| *k);
+        self.data.dedup_by(|later, earlier| {
+            if later.0 == earlier.0 {
+                std::mem::swap(later, earlier);
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    fn on_remove_batch(&mut self, ts: Vec<T>) -> Vec<T> {
+        let keyf = &self.key_func;
+        let keys: BTreeSet<K> = ts.iter().map(keyf).collect();
+        let mut found = BTreeSet::new();
+        self.data.retain(|(k, _)| {
+            if keys.contains(k) {
+                found.insert(*k);
+                false
+            } else {
+                true
+            }
+        });
+        ts.into_iter()
+            .filter(|t| !found.contains(&keyf(t)))
+            .collect()
+    }
+
+    fn on_replace(&mut self, old: &T, new: T) -> bool {
+        let old_key = (self.key_func)(old);
+        let new_key = (self.key_func)(&new);
+        let removed = if old_key != new_key {
+            self.remove_keyed(old_key)
+        } else {
+            self.data
+                .binary_search_by_key(&old_key, |(k, _)| *k)
+                .is_ok()
+        };
+        self.insert_keyed(new_key, new);
+        removed
     }
 }
 
-pub struct OrderedVecView<'a, T: Data + 'a, K: Copy + Eq + Ord + 'a> {
-    data: Vec<(K, T)>,
-    key_func: Box<dyn Fn(&T) -> K + 'a>,
-    collector: Collector<'a, SetChange<T>>,
+impl<'a, T: Data + 'a, K: Copy + Eq + Ord + 'a> IterableState for OrderedState<'a, T, K> {
+    type Item = (K, T);
+
+    fn iter_items<'b>(&'b self) -> Box<dyn Iterator<Item = &'b (K, T)> + 'b> {
+        Box::new(self.data.iter())
+    }
 }
 
+pub type OrderedVecView<'a, T, K> = IncrementalView<'a, T, OrderedState<'a, T, K>>;
+
 impl<'a, T: Data + 'a, K: Copy + Eq + Ord + 'a> OrderedVecView<'a, T, K> {
     pub fn new<KF: Fn(&T) -> K + 'a>(collector: Collector<'a, SetChange<T>>, key_func: KF) -> Self {
-        OrderedVecView {
-            data: Vec::new(),
-            key_func: Box::new(key_func),
+        Self::from_state(
             collector,
-        }
+            OrderedState {
+                key_func: Box::new(key_func),
+                data: Vec::new(),
+            },
+        )
     }
 
     pub fn unchanged_data(&self) -> &Vec<(K, T)> {
-        &self.data
+        &self.state_unchanged().data
     }
 
     pub fn data(&mut self) -> &Vec<(K, T)> {
-        self.update();
-        self.unchanged_data()
+        &self.state().data
     }
 }
 
-impl<'a, T: Data + 'a, K: Copy + Eq + Ord + 'a> View<'a, T> for OrderedVecView<'a, T, K> {
-    type Item = (K, T);
+type ViewCheck<'a, T> = Box<dyn FnMut(&[T]) -> Option<bool> + 'a>;
 
-    fn update(&mut self) {
-        self.collector.update();
-        let store = &mut self.data;
-        let keyf = &self.key_func;
+// Replays the change log into a vec and compares views against it as multisets
+pub struct Validator<'a, T: Data + 'a> {
+    log: VecView<'a, T>,
+    views: Vec<ViewCheck<'a, T>>,
+}
 
-        self.collector
-            .items
-            .drain(..)
-            .for_each(|change| match change {
-                SetChange::Added(t) => {
-                    let key = keyf(&t);
-                    match store.binary_search_by_key(&key, |(k, _)| *k) {
-                        Ok(existing_idx) => store[existing_idx] = (key, t),
-                        Err(new_idx) => store.insert(new_idx, (key, t)),
-                    }
-                }
-                SetChange::Removed(t) => {
-                    let key = keyf(&t);
-                    if let Ok(existing_idx) = store.binary_search_by_key(&key, |(k, _)| *k) {
-                        store.remove(existing_idx);
-                    } else {
-                        //TODO: Panic?
-                    }
-                }
-                SetChange::Clear => store.clear(),
-            });
-        self.collector.clear();
+impl<'a, T: Data + 'a> Validator<'a, T> {
+    pub fn new(collector: Collector<'a, SetChange<T>>) -> Self {
+        Self {
+            log: VecView::new(collector),
+            views: Vec::new(),
+        }
     }
 
-    fn iter_unchanged<'b>(&'b self) -> Box<dyn Iterator<Item = &Self::Item> + 'b> {
-        Box::new(self.data.iter())
+    pub fn check<V: View<'a, T, Item = T>>(&mut self, view: &mut V) -> bool
+    where
+        T: Hash + Eq,
+    {
+        self.check_by(view, |t| t.cheap_clone())
+    }
+
+    pub fn check_by<V: View<'a, T>, P: Fn(&T) -> V::Item>(
+        &mut self,
+        view: &mut V,
+        project: P,
+    ) -> bool
+    where
+        V::Item: Hash + Eq,
+    {
+        same_items(self.log.data().iter().map(project), view.iter())
+    }
+
+    // Attached views are only held weakly, dropping a view detaches it
+    pub fn attach<V: View<'a, T, Item = T> + 'a>(&mut self, view: &Rc<RefCell<V>>)
+    where
+        T: Hash + Eq,
+    {
+        self.attach_by(view, |t| t.cheap_clone())
+    }
+
+    pub fn attach_by<V: View<'a, T> + 'a, P: Fn(&T) -> V::Item + 'a>(
+        &mut self,
+        view: &Rc<RefCell<V>>,
+        project: P,
+    ) where
+        V::Item: Hash + Eq,
+    {
+        let view = Rc::downgrade(view);
+        self.views.push(Box::new(move |log| {
+            let view = view.upgrade()?;
+            let mut view = view.borrow_mut();
+            Some(same_items(log.iter().map(&project), view.iter()))
+        }));
+    }
+
+    // Every attached view is checked, even after the first inconsistent one
+    pub fn check_all(&mut self) -> bool {
+        let log = self.log.data();
+        let mut consistent = true;
+        self.views.retain_mut(|check| match check(log) {
+            Some(result) => {
+                consistent &= result;
+                true
+            }
+            None => false,
+        });
+        consistent
+    }
+
+    pub fn debug_check<V: View<'a, T, Item = T>>(&mut self, view: &mut V)
+    where
+        T: Hash + Eq,
+    {
+        if cfg!(debug_assertions) {
+            assert!(self.check(view), "View is inconsistent with the change log");
+        }
+    }
+
+    pub fn debug_check_all(&mut self) {
+        if cfg!(debug_assertions) {
+            assert!(
+                self.check_all(),
+                "An attached view is inconsistent with the change log"
+            );
+        }
+    }
+}
+
+fn same_items<'b, I: Hash + Eq + 'b>(
+    expected: impl Iterator<Item = I>,
+    actual: impl Iterator<Item = &'b I>,
+) -> bool {
+    let mut counts: HashMap<I, usize> = HashMap::new();
+    for item in expected {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+    for item in actual {
+        match counts.get_mut(item) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => return false,
+        }
     }
+    counts.values().all(|count| *count == 0)
 }
 
 #[cfg(test)]
@@ -846,4 +1378,359 @@ mod tests {
         assert_eq!(view1.data(), &vec![0, 1, 3]);
         assert_eq!(view2.data(), &vec![0, 1, 3]);
     }
+
+    #[test]
+    fn test_diff_hash_set() {
+        let world = World::new();
+        let (set, v) = mutable(&world, HashSet::new());
+        let mut view = v.diff_to_collection().view_set_btree();
+        assert!(view.data().is_empty());
+        set(vec![1, 2, 3].into_iter().collect());
+        assert_eq!(view.data(), &vec![1, 2, 3].into_iter().collect());
+        set(vec![2, 3, 4].into_iter().collect());
+        assert_eq!(view.data(), &vec![2, 3, 4].into_iter().collect());
+        set(HashSet::new());
+        assert!(view.data().is_empty());
+    }
+
+    #[test]
+    fn test_diff_vec() {
+        let world = World::new();
+        let (set, v) = mutable(&world, vec![1, 1, 2]);
+        let mut view = v.diff_to_collection().view_vec();
+        assert_eq!(view.data(), &vec![1, 1, 2]);
+        set(vec![1, 2, 3]);
+        assert_eq!(view.data(), &vec![1, 2, 3]);
+        set(vec![3]);
+        assert_eq!(view.data(), &vec![3]);
+    }
+
+    #[test]
+    fn test_diff_vec_by_key() {
+        let world = World::new();
+        let (set, v) = mutable(&world, vec![(1, "a"), (2, "b")]);
+        let diffed = v.diff_to_collection_by_key(|(k, _)| *k);
+        let mut changes = diffed.collect();
+        let mut view = diffed.view_map_btree(|(k, _)| *k, |(_, v)| v);
+        assert_eq!(
+            view.data().values().cloned().collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        changes.update();
+        changes.clear();
+        set(vec![(1, "a"), (2, "c"), (3, "d")]);
+        assert_eq!(
+            view.data().values().cloned().collect::<Vec<_>>(),
+            vec!["a", "c", "d"]
+        );
+        changes.update();
+        assert_eq!(
+            changes.iter().cloned().collect::<Vec<_>>(),
+            vec![
+                SetChange::Replaced((2, "b"), (2, "c")),
+                SetChange::AddedBatch(vec![(3, "d")])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        setp.add(1);
+        let snapshot = setp.signal().snapshot();
+        assert_eq!(read_once(&snapshot), vec![1]);
+        setp.add(2);
+        setp.add(3);
+        assert_eq!(read_once(&snapshot), vec![1, 2, 3]);
+        setp.remove(2);
+        assert_eq!(read_once(&snapshot), vec![1, 3]);
+        setp.clear();
+        assert!(read_once(&snapshot).is_empty());
+    }
+
+    #[test]
+    fn test_batch_changes() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let mut changes = setp.signal().collect();
+        let mut set_view = setp.signal().view_set_btree();
+        let mut vec_view = setp.signal().view_vec();
+        setp.extend(0..5);
+        setp.extend(Vec::new());
+        changes.update();
+        assert_eq!(
+            changes.iter().cloned().collect::<Vec<_>>(),
+            vec![SetChange::AddedBatch(vec![0, 1, 2, 3, 4])]
+        );
+        assert_eq!(set_view.data(), &(0..5).collect());
+        setp.remove_all(vec![1, 3]);
+        assert_eq!(vec_view.data(), &vec![0, 2, 4]);
+        assert_eq!(set_view.data(), &vec![0, 2, 4].into_iter().collect());
+    }
+
+    #[test]
+    fn test_batch_map_filter() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let mapped = setp.signal().map(|i| i * 2);
+        let mut filtered = setp.signal().filter(|i| i % 2 == 0).view_vec();
+        let mut changes = mapped.collect();
+        setp.extend(vec![1, 2, 3, 4]);
+        changes.update();
+        assert_eq!(
+            changes.iter().cloned().collect::<Vec<_>>(),
+            vec![SetChange::AddedBatch(vec![2, 4, 6, 8])]
+        );
+        assert_eq!(filtered.data(), &vec![2, 4]);
+        setp.remove_all(vec![1, 2]);
+        assert_eq!(filtered.data(), &vec![4]);
+    }
+
+    #[test]
+    fn test_batch_ordered() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let mut view = setp.signal().view_vec_sorted(|i| *i / 2);
+        setp.add(4);
+        setp.extend(vec![7, 0, 5, 1]);
+        assert_eq!(view_values(&mut view), vec![1, 5, 7]);
+        setp.remove_all(vec![1, 7]);
+        assert_eq!(view_values(&mut view), vec![5]);
+    }
+
+    #[test]
+    fn test_later_attachment_batch() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        setp.extend(vec![0, 1, 2]);
+        let mut changes = setp.signal().collect();
+        changes.update();
+        assert_eq!(
+            changes.iter().cloned().collect::<Vec<_>>(),
+            vec![SetChange::AddedBatch(vec![0, 1, 2])]
+        );
+    }
+
+    #[test]
+    fn test_replace() {
+        let world = World::new();
+        let mut setp: CollectionPortal<(i32, i32)> =
+            CollectionPortal::new::<VecView<(i32, i32)>>(&world);
+        let mut vec_view = setp.signal().view_vec();
+        let mut map_view = setp.signal().view_map_hash(|(a, _)| *a, |(_, b)| b);
+        let mut set_view = setp.signal().view_set_hash();
+        setp.add((1, 1));
+        setp.add((2, 2));
+        assert_eq!(vec_view.data(), &vec![(1, 1), (2, 2)]);
+        setp.replace((1, 1), (1, 3));
+        assert_eq!(vec_view.data(), &vec![(1, 3), (2, 2)]);
+        assert_eq!(map_view.data()[&1], 3);
+        assert!(set_view.data().contains(&(1, 3)));
+        assert!(!set_view.data().contains(&(1, 1)));
+        setp.replace((2, 2), (4, 2));
+        assert!(!map_view.data().contains_key(&2));
+        assert_eq!(map_view.data()[&4], 2);
+    }
+
+    #[test]
+    fn test_replace_ordered() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let mut view = setp.signal().view_vec_sorted(|i| *i / 2);
+        let mut changes = setp.signal().collect();
+        setp.extend(vec![0, 2, 4]);
+        setp.replace(2, 3);
+        assert_eq!(view_values(&mut view), vec![0, 3, 4]);
+        setp.replace(3, 7);
+        assert_eq!(view_values(&mut view), vec![0, 4, 7]);
+        changes.update();
+        assert_eq!(
+            changes.iter().cloned().collect::<Vec<_>>(),
+            vec![
+                SetChange::AddedBatch(vec![0, 2, 4]),
+                SetChange::Replaced(2, 3),
+                SetChange::Replaced(3, 7)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_filter() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let filtered = setp.signal().filter(|i| i % 2 == 0);
+        let mut changes = filtered.collect();
+        setp.add(2);
+        setp.replace(2, 4);
+        setp.replace(4, 5);
+        setp.replace(5, 7);
+        setp.replace(7, 8);
+        changes.update();
+        assert_eq!(
+            changes.iter().cloned().collect::<Vec<_>>(),
+            vec![
+                SetChange::Added(2),
+                SetChange::Replaced(2, 4),
+                SetChange::Removed(4),
+                SetChange::Added(8)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_where() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let mut view = setp.signal().view_vec();
+        setp.extend(vec![1, 2, 3, 4]);
+        setp.update_where(|i| i % 2 == 0, |i| i * 10);
+        assert_eq!(view.data(), &vec![1, 20, 3, 40]);
+        setp.update_where(|i| *i > 10, |i| *i);
+        assert_eq!(view.data(), &vec![1, 20, 3, 40]);
+    }
+
+    #[test]
+    fn test_inconsistency_collect() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let mut set_view = setp
+            .signal()
+            .view_set_hash()
+            .with_policy(InconsistencyPolicy::Collect);
+        let mut sorted_view = setp
+            .signal()
+            .view_vec_sorted(|i| *i)
+            .with_policy(InconsistencyPolicy::Collect);
+        let mut ignoring_view = setp.signal().view_vec();
+        setp.add(1);
+        setp.remove(2);
+        setp.remove_all(vec![1, 3]);
+        set_view.update();
+        sorted_view.update();
+        ignoring_view.update();
+        let expected = vec![
+            Inconsistency::MissingRemoval(2),
+            Inconsistency::MissingRemoval(3),
+        ];
+        assert_eq!(set_view.inconsistencies(), expected.as_slice());
+        assert_eq!(sorted_view.inconsistencies(), expected.as_slice());
+        assert!(ignoring_view.inconsistencies().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inconsistency_panic() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let mut view = setp
+            .signal()
+            .view_vec_indexed(|i| *i as usize, |i| i)
+            .with_policy(InconsistencyPolicy::Panic);
+        setp.replace(4, 5);
+        view.update();
+    }
+
+    #[test]
+    fn test_validator() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let mut validator = setp.signal().validator();
+        let mut vec_view = setp.signal().view_vec();
+        let mut set_view = setp.signal().view_set_hash();
+        let mut map_view = setp.signal().view_map_btree(|i| *i, |i| i * 2);
+        setp.extend(vec![1, 2, 3]);
+        setp.replace(2, 4);
+        assert!(validator.check(&mut vec_view));
+        assert!(validator.check(&mut set_view));
+        assert!(validator.check_by(&mut map_view, |i| i * 2));
+        validator.debug_check(&mut set_view);
+        setp.add(1);
+        setp.remove(1);
+        assert!(validator.check(&mut vec_view));
+        assert!(!validator.check(&mut set_view));
+    }
+
+    #[test]
+    fn test_validator_multiplicity() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let mut validator = setp.signal().validator();
+        let mut set_view = setp.signal().view_set_hash();
+        setp.add(1);
+        assert!(validator.check(&mut set_view));
+        setp.add(1);
+        assert!(!validator.check(&mut set_view));
+    }
+
+    #[test]
+    fn test_validator_attached_views() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let mut validator = setp.signal().validator();
+        let vec_view = Rc::new(RefCell::new(setp.signal().view_vec()));
+        let set_view = Rc::new(RefCell::new(setp.signal().view_set_btree()));
+        let map_view = Rc::new(RefCell::new(setp.signal().view_map_hash(|i| *i, |i| i * 2)));
+        validator.attach(&vec_view);
+        validator.attach(&set_view);
+        validator.attach_by(&map_view, |i| i * 2);
+        setp.extend(vec![1, 2, 3]);
+        assert!(validator.check_all());
+        validator.debug_check_all();
+        setp.add(2);
+        assert!(!validator.check_all());
+        drop(set_view);
+        drop(map_view);
+        assert!(validator.check_all());
+        assert_eq!(vec_view.borrow_mut().data().len(), 4);
+    }
+
+    #[derive(Default)]
+    struct PrefixIndex {
+        by_letter: BTreeMap<char, BTreeSet<String>>,
+    }
+
+    impl IncrementalState<String> for PrefixIndex {
+        fn on_add(&mut self, t: String) {
+            if let Some(c) = t.chars().next() {
+                self.by_letter.entry(c).or_default().insert(t);
+            }
+        }
+
+        fn on_remove(&mut self, t: &String) -> bool {
+            let c = t.chars().next();
+            match c.and_then(|c| self.by_letter.get_mut(&c)) {
+                Some(set) => set.remove(t),
+                None => false,
+            }
+        }
+
+        fn on_clear(&mut self) {
+            self.by_letter.clear()
+        }
+    }
+
+    #[test]
+    fn test_incremental_view() {
+        let world = World::new();
+        let mut setp: CollectionPortal<String> = CollectionPortal::new::<VecView<String>>(&world);
+        let mut view = setp
+            .signal()
+            .view_incremental(PrefixIndex::default())
+            .with_policy(InconsistencyPolicy::Collect);
+        setp.extend(vec![
+            "apple".to_owned(),
+            "avocado".to_owned(),
+            "banana".to_owned(),
+        ]);
+        assert_eq!(view.state().by_letter.get(&'a').map(|s| s.len()), Some(2));
+        setp.replace("apple".to_owned(), "blueberry".to_owned());
+        setp.remove("cherry".to_owned());
+        let state = view.state();
+        assert_eq!(state.by_letter.get(&'a').map(|s| s.len()), Some(1));
+        assert_eq!(state.by_letter.get(&'b').map(|s| s.len()), Some(2));
+        assert_eq!(view.inconsistencies().len(), 1);
+        setp.clear();
+        assert!(view.state().by_letter.is_empty());
+    }
 }
@@ -227,6 +227,7 @@ impl<T> IndexMut<NodeIndex> for Graph<T> {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use crate::graph::Graph;
 
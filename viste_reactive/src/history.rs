@@ -0,0 +1,231 @@
+use crate::values::mutable::Mutable;
+use crate::*;
+
+struct History<T: Data> {
+    world: World,
+    value: Rc<RefCell<Mutable<T>>>,
+    undo: VecDeque<(T, Option<u64>)>,
+    redo: Vec<T>,
+    limit: Option<usize>,
+    can_undo: Rc<RefCell<Mutable<bool>>>,
+    can_redo: Rc<RefCell<Mutable<bool>>>,
+}
+
+impl<T: Data> History<T> {
+    fn set(&mut self, value: T) {
+        let current = self.value.borrow().get();
+        if !value.changed(&current) {
+            return;
+        }
+        let transaction = self.world.current_transaction();
+        let same_step = match self.undo.back() {
+            Some((_, last)) => transaction.is_some() && *last == transaction,
+            None => false,
+        };
+        if !same_step {
+            self.push_undo((current, transaction));
+        }
+        self.redo.clear();
+        self.value.borrow_mut().set(value);
+        self.update_flags();
+    }
+
+    fn undo(&mut self) -> bool {
+        match self.undo.pop_back() {
+            Some((previous, _)) => {
+                let current = self.value.borrow().get();
+                self.redo.push(current);
+                self.value.borrow_mut().set(previous);
+                self.update_flags();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(next) => {
+                let current = self.value.borrow().get();
+                self.push_undo((current, None));
+                self.value.borrow_mut().set(next);
+                self.update_flags();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // The oldest step is forgotten once the limit is reached
+    fn push_undo(&mut self, step: (T, Option<u64>)) {
+        if self.limit == Some(self.undo.len()) {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(step);
+    }
+
+    fn update_flags(&mut self) {
+        self.can_undo.borrow_mut().set(!self.undo.is_empty());
+        self.can_redo.borrow_mut().set(!self.redo.is_empty());
+    }
+}
+
+impl<T: Data> Writable<T> for History<T> {
+    fn write(&mut self, value: T) {
+        self.set(value)
+    }
+}
+
+// Every change is one undo step, except for changes made inside the same World transaction.
+// signal() is read only, writes through writable() and anything focused on it are recorded as well.
+// The number of undo steps is unbounded unless the history is created with_limit.
+pub struct HistoryMutable<'a, T: Data + 'a> {
+    history: Rc<RefCell<History<T>>>,
+    signal: WritableSignal<'a, T>,
+}
+
+impl<'a, T: Data + 'a> HistoryMutable<'a, T> {
+    pub fn new(world: &World, initial: T) -> Self {
+        Self::create(world, initial, None)
+    }
+
+    pub fn with_limit(world: &World, initial: T, limit: usize) -> Self {
+        assert!(limit > 0, "History limit must be at least 1");
+        Self::create(world, initial, Some(limit))
+    }
+
+    fn create(world: &World, initial: T, limit: Option<usize>) -> Self {
+        let value = Rc::new(RefCell::new(Mutable::new(world.clone(), initial)));
+        let history = Rc::new(RefCell::new(History {
+            world: world.clone(),
+            value: value.clone(),
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit,
+            can_undo: Rc::new(RefCell::new(Mutable::new(world.clone(), false))),
+            can_redo: Rc::new(RefCell::new(Mutable::new(world.clone(), false))),
+        }));
+        Self {
+            signal: WritableSignal::new(ValueSignal::new(Signal(value)), history.clone()),
+            history,
+        }
+    }
+
+    pub fn signal(&self) -> &ValueSignal<'a, T> {
+        &self.signal
+    }
+
+    pub fn writable(&self) -> WritableSignal<'a, T> {
+        self.signal.clone()
+    }
+
+    pub fn can_undo(&self) -> ValueSignal<'a, bool> {
+        ValueSignal::new(Signal(self.history.borrow().can_undo.clone()))
+    }
+
+    pub fn can_redo(&self) -> ValueSignal<'a, bool> {
+        ValueSignal::new(Signal(self.history.borrow().can_redo.clone()))
+    }
+
+    pub fn set(&self, value: T) {
+        self.history.borrow_mut().set(value)
+    }
+
+    // Returns false if there was nothing to undo
+    pub fn undo(&self) -> bool {
+        self.history.borrow_mut().undo()
+    }
+
+    // Returns false if there was nothing to redo
+    pub fn redo(&self) -> bool {
+        self.history.borrow_mut().redo()
+    }
+}
+
+pub fn mutable_with_history<'a, T: Data + 'a>(world: &World, initial: T) -> HistoryMutable<'a, T> {
+    HistoryMutable::new(world, initial)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_redo() {
+        let world = World::new();
+        let text = mutable_with_history(&world, "".to_owned());
+        let can_undo = text.can_undo();
+        let can_redo = text.can_redo();
+        let length = text.signal().map(|s| s.len());
+        assert!(!read_once(&can_undo));
+        assert!(!text.undo());
+        text.set("a".to_owned());
+        text.set("ab".to_owned());
+        text.set("ab".to_owned());
+        assert_eq!(read_once(&length), 2);
+        assert!(read_once(&can_undo));
+        assert!(text.undo());
+        assert_eq!(read_once(text.signal()), "a");
+        assert!(read_once(&can_redo));
+        assert!(text.redo());
+        assert_eq!(read_once(text.signal()), "ab");
+        assert!(!read_once(&can_redo));
+        text.undo();
+        text.undo();
+        assert_eq!(read_once(&length), 0);
+        assert!(!read_once(&can_undo));
+        // A new change discards everything that could have been redone
+        text.set("c".to_owned());
+        assert!(!text.redo());
+        assert!(!read_once(&can_redo));
+    }
+
+    #[test]
+    fn test_transaction_is_one_step() {
+        let world = World::new();
+        let counter = mutable_with_history(&world, 0);
+        counter.set(1);
+        world.transaction(|| {
+            counter.set(2);
+            world.transaction(|| counter.set(3));
+            counter.set(4);
+        });
+        world.transaction(|| counter.set(5));
+        assert!(counter.undo());
+        assert_eq!(read_once(counter.signal()), 4);
+        assert!(counter.undo());
+        assert_eq!(read_once(counter.signal()), 1);
+        assert!(counter.redo());
+        assert_eq!(read_once(counter.signal()), 4);
+        assert_eq!(world.current_transaction(), None);
+    }
+
+    #[test]
+    fn test_focused_writes_are_recorded() {
+        let world = World::new();
+        let pair = mutable_with_history(&world, (1, 2));
+        let first = pair.writable().focus(|p| p.0, |p, v| p.0 = v);
+        first.write(5);
+        assert_eq!(read_once(pair.signal()), (5, 2));
+        pair.writable().write((5, 3));
+        assert!(pair.undo());
+        assert_eq!(read_once(pair.signal()), (5, 2));
+        assert!(pair.undo());
+        assert_eq!(read_once(&first), 1);
+        assert!(!pair.undo());
+    }
+
+    #[test]
+    fn test_history_limit() {
+        let world = World::new();
+        let counter = HistoryMutable::with_limit(&world, 0, 2);
+        (1..5).for_each(|v| counter.set(v));
+        assert!(counter.undo());
+        assert!(counter.undo());
+        assert!(!counter.undo());
+        assert_eq!(read_once(counter.signal()), 2);
+        assert!(counter.redo());
+        assert!(counter.redo());
+        assert_eq!(read_once(counter.signal()), 4);
+    }
+}
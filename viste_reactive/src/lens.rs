@@ -0,0 +1,13 @@
+// Reads and writes one part of a larger value, usually generated with #[derive(Lens)] from viste_macros
+pub struct Lens<S, F> {
+    pub get: fn(&S) -> F,
+    pub set: fn(&mut S, F),
+}
+
+impl<S, F> Clone for Lens<S, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, F> Copy for Lens<S, F> {}
@@ -1,13 +1,19 @@
 use crate::graph::{Graph, NodeIndex, SearchContinuation};
+use crate::lens::Lens;
 use crate::readers::{CachedReader, ChangeReader, Reader, StreamReader};
+use crate::snapshot::{Snapshot, Stateful};
+use crate::stores::{BufferOverflow, OverflowPolicy, Store};
 use crate::streams::combine_mapper::CombineMapper;
 use crate::streams::counter::Counter;
 use crate::streams::from_iter::FromIter;
 use crate::streams::last::Last;
 use crate::streams::many::Many;
 use crate::streams::portal::Portal;
+use crate::streams::router::{Routed, RoutedKeys, Router};
 use crate::streams::zip_mapper::ZipMapper;
+use crate::time::{Clock, VirtualClock};
 use crate::values::binder::{Binder, Binder2};
+use crate::values::computed::Computed;
 use crate::values::constant::Constant;
 use crate::values::filter::Filter;
 use crate::values::folder::Folder;
@@ -16,18 +22,33 @@ use crate::values::mutable::Mutable;
 use log::info;
 use slab::Slab;
 use std::cell::RefCell;
-use std::collections::VecDeque;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
 use std::fmt::{Debug, Formatter};
+use std::hash::Hash;
 use std::marker::PhantomData;
 use std::mem::replace;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
+use std::task::Waker;
+use std::time::Duration;
 use tinyvec::TinyVec;
 
+pub use crate::values::computed::ComputeContext;
+
+#[cfg(feature = "futures")]
+pub mod async_adapters;
+pub mod channels;
 pub mod collections;
 pub mod graph;
+pub mod history;
+pub mod lens;
 pub mod readers;
+#[cfg(feature = "serde")]
+pub mod recording;
+pub mod snapshot;
 pub mod stores;
 mod streams;
+pub mod time;
 mod values;
 
 pub trait Data: PartialEq + Clone {
@@ -110,25 +131,171 @@ pub enum DirtyingCause {
 
 struct WorldData {
     dependencies: Graph<DirtyFlag>,
+    clock: Box<dyn Clock>,
+    timers: Vec<(Duration, NodeIndex)>,
+    wakers: HashMap<NodeIndex, Waker>,
+    inputs: Vec<Poller>,
+    outputs: Vec<Poller>,
+    stateful: HashMap<String, Weak<RefCell<dyn Stateful>>>,
+    transaction_depth: usize,
+    transaction_count: u64,
 }
 
+// Called on every World::poll, returns false once it should be unregistered
+pub type Poller = Box<dyn FnMut() -> bool>;
+
 pub struct World(Rc<RefCell<WorldData>>);
 
 impl World {
     pub fn new() -> Self {
+        Self::with_clock(VirtualClock::new())
+    }
+
+    pub fn with_clock<C: Clock + 'static>(clock: C) -> Self {
         World(Rc::new(RefCell::new(WorldData {
             dependencies: Graph::new(),
+            clock: Box::new(clock),
+            timers: Vec::new(),
+            wakers: HashMap::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            stateful: HashMap::new(),
+            transaction_depth: 0,
+            transaction_count: 0,
         })))
     }
 
-    pub fn mark_dirty(&self, node: NodeIndex, cause: DirtyingCause) {
+    pub fn now(&self) -> Duration {
+        self.0.borrow().clock.now()
+    }
+
+    // Marks the node dirty on the first tick at or after the given time
+    pub fn schedule(&self, node: NodeIndex, at: Duration) {
+        self.0.borrow_mut().timers.push((at, node));
+    }
+
+    pub fn tick(&self) {
+        let due: Vec<NodeIndex> = {
+            let mut wd = self.0.borrow_mut();
+            let now = wd.clock.now();
+            let mut due = Vec::new();
+            wd.timers.retain(|(at, node)| {
+                if *at <= now {
+                    due.push(*node);
+                    false
+                } else {
+                    true
+                }
+            });
+            due
+        };
+        for node in due {
+            self.mark_dirty(node, DirtyingCause::External);
+        }
+    }
+
+    pub fn register_input(&self, poller: Poller) {
+        self.0.borrow_mut().inputs.push(poller);
+    }
+
+    pub fn register_output(&self, poller: Poller) {
+        self.0.borrow_mut().outputs.push(poller);
+    }
+
+    // Runs all input pollers first, so outputs already see the values they brought in
+    pub fn poll(&self) {
+        self.run_pollers(|wd| &mut wd.inputs);
+        self.run_pollers(|wd| &mut wd.outputs);
+    }
+
+    fn run_pollers(&self, select: fn(&mut WorldData) -> &mut Vec<Poller>) {
+        let mut pollers = std::mem::take(select(&mut self.0.borrow_mut()));
+        pollers.retain_mut(|poller| poller());
+        let mut wd = self.0.borrow_mut();
+        let registered = select(&mut wd);
+        pollers.append(registered);
+        *registered = pollers;
+    }
+
+    // Keys have to be unique among the live stateful nodes of a world
+    pub fn register_stateful(&self, key: &str, node: Weak<RefCell<dyn Stateful>>) {
+        let mut wd = self.0.borrow_mut();
+        if let Some(existing) = wd.stateful.get(key) {
+            assert!(
+                existing.strong_count() == 0,
+                "Stateful key {} is already registered",
+                key
+            );
+        }
+        wd.stateful.insert(key.to_owned(), node);
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
         let mut wd = self.0.borrow_mut();
-        let old_dirty = &wd.dependencies[node];
-        if !old_dirty.is_dirty() {
+        wd.stateful.retain(|_, node| node.strong_count() > 0);
+        Snapshot::new(
+            wd.stateful
+                .iter()
+                .filter_map(|(key, node)| Some((key.clone(), node.upgrade()?.borrow().save())))
+                .collect(),
+        )
+    }
+
+    // Keys without a live node are ignored, restored nodes are marked dirty
+    pub fn restore(&self, snapshot: &Snapshot) {
+        let nodes: Vec<_> = {
+            let wd = self.0.borrow();
+            snapshot
+                .iter()
+                .filter_map(|(key, state)| Some((wd.stateful.get(key)?.upgrade()?, state)))
+                .collect()
+        };
+        for (node, state) in nodes {
+            node.borrow_mut().restore(&**state);
+        }
+    }
+
+    // Nested transactions are part of the outermost one
+    pub fn transaction<R, F: FnOnce() -> R>(&self, f: F) -> R {
+        {
+            let mut wd = self.0.borrow_mut();
+            if wd.transaction_depth == 0 {
+                wd.transaction_count += 1;
+            }
+            wd.transaction_depth += 1;
+        }
+        let _guard = TransactionGuard(self);
+        f()
+    }
+
+    pub fn current_transaction(&self) -> Option<u64> {
+        let wd = self.0.borrow();
+        if wd.transaction_depth > 0 {
+            Some(wd.transaction_count)
+        } else {
+            None
+        }
+    }
+
+    // Moves a VirtualClock forward, with an InstantClock this is the same as tick
+    pub fn advance_time(&self, by: Duration) {
+        self.0.borrow_mut().clock.advance(by);
+        self.tick();
+    }
+
+    pub fn mark_dirty(&self, node: NodeIndex, cause: DirtyingCause) {
+        let to_wake = {
+            let mut wd = self.0.borrow_mut();
+            let old_dirty = &wd.dependencies[node];
+            if old_dirty.is_dirty() {
+                return;
+            }
+            let mut dirtied = Vec::new();
             wd.dependencies.search_children_mut(
                 |child, child_idx, state| {
                     let was_dirtied = child.mark(state);
                     if was_dirtied {
+                        dirtied.push(child_idx);
                         SearchContinuation::Continue(DirtyingCause::Parent(child_idx))
                     } else {
                         SearchContinuation::Stop
@@ -137,6 +304,31 @@ impl World {
                 node,
                 cause,
             );
+            let mut to_wake = Vec::new();
+            if !wd.wakers.is_empty() {
+                to_wake.extend(dirtied.iter().filter_map(|n| wd.wakers.remove(n)));
+            }
+            to_wake
+        };
+        to_wake.into_iter().for_each(Waker::wake);
+    }
+
+    // Wakes the task once the node is dirtied, immediately if it already is.
+    // Only one waker is kept per node, polling again from the same task does not register another one.
+    pub fn wake_on_dirty(&self, node: NodeIndex, waker: &Waker) {
+        if self.is_dirty(node) {
+            waker.wake_by_ref();
+            return;
+        }
+        match self.0.borrow_mut().wakers.entry(node) {
+            Entry::Occupied(mut registered) => {
+                if !registered.get().will_wake(waker) {
+                    registered.insert(waker.clone());
+                }
+            }
+            Entry::Vacant(vacant) => {
+                vacant.insert(waker.clone());
+            }
         }
     }
 
@@ -164,7 +356,10 @@ impl World {
     }
 
     pub fn destroy_node(&self, node: NodeIndex) {
-        self.0.borrow_mut().dependencies.remove_node(node);
+        let mut wd = self.0.borrow_mut();
+        wd.dependencies.remove_node(node);
+        wd.timers.retain(|(_, n)| *n != node);
+        wd.wakers.remove(&node);
     }
 
     pub fn add_dependency(&self, parent: NodeIndex, child: NodeIndex) {
@@ -177,6 +372,14 @@ impl World {
     }
 }
 
+struct TransactionGuard<'w>(&'w World);
+
+impl<'w> Drop for TransactionGuard<'w> {
+    fn drop(&mut self) {
+        self.0 .0.borrow_mut().transaction_depth -= 1;
+    }
+}
+
 impl Debug for World {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", &self.0.borrow().dependencies)
@@ -215,6 +418,34 @@ impl<T: Data> SingleComputationResult<T> {
     }
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum StreamEvent<T> {
+    Item(T),
+    Pending,
+    Complete,
+}
+
+impl<T> StreamEvent<T> {
+    pub fn item(self) -> Option<T> {
+        match self {
+            StreamEvent::Item(t) => Some(t),
+            _ => None,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        matches!(self, StreamEvent::Complete)
+    }
+
+    pub fn map<R, F: FnOnce(T) -> R>(self, f: F) -> StreamEvent<R> {
+        match self {
+            StreamEvent::Item(t) => StreamEvent::Item(f(t)),
+            StreamEvent::Pending => StreamEvent::Pending,
+            StreamEvent::Complete => StreamEvent::Complete,
+        }
+    }
+}
+
 pub trait ComputationCore {
     type ComputationResult;
     fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult;
@@ -225,6 +456,67 @@ pub trait ComputationCore {
     fn is_dirty(&self) -> bool;
     fn world(&self) -> World;
     fn node(&self) -> NodeIndex;
+
+    // Number of values the reader missed because a bounded buffer overflowed
+    fn take_lag(&mut self, _reader: ReaderToken) -> usize {
+        0
+    }
+}
+
+// Implemented by mutable state and by signals focused on it, see WritableSignal
+pub trait Writable<T> {
+    fn write(&mut self, value: T);
+}
+
+// Nodes that own a NodeState and a single store get ComputationCore by implementing this,
+// only the computation itself has to be written
+pub trait Operator {
+    type Output;
+    type Store: Store;
+
+    fn node_state(&self) -> &NodeState;
+    fn store(&mut self) -> &mut Self::Store;
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output;
+}
+
+impl<O: Operator> ComputationCore for O {
+    type ComputationResult = O::Output;
+
+    fn compute(&mut self, reader: ReaderToken) -> O::Output {
+        self.evaluate(reader)
+    }
+
+    fn create_reader(&mut self) -> ReaderToken {
+        self.store().create_reader()
+    }
+
+    fn destroy_reader(&mut self, reader: ReaderToken) {
+        self.store().destroy_reader(reader)
+    }
+
+    fn add_dependency(&mut self, child: NodeIndex) {
+        self.node_state().add_dependency(child)
+    }
+
+    fn remove_dependency(&mut self, child: NodeIndex) {
+        self.node_state().remove_dependency(child)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.node_state().is_dirty()
+    }
+
+    fn world(&self) -> World {
+        self.node_state().world().clone()
+    }
+
+    fn node(&self) -> NodeIndex {
+        self.node_state().node()
+    }
+
+    fn take_lag(&mut self, reader: ReaderToken) -> usize {
+        self.store().take_lag(reader)
+    }
 }
 
 pub struct Signal<'a, CR: 'a>(Rc<RefCell<dyn ComputationCore<ComputationResult = CR> + 'a>>);
@@ -265,6 +557,10 @@ impl<'a, CR: 'a> Signal<'a, CR> {
     pub fn node(&self) -> NodeIndex {
         self.0.borrow().node()
     }
+
+    pub fn take_lag(&self, reader: ReaderToken) -> usize {
+        self.0.borrow_mut().take_lag(reader)
+    }
 }
 
 impl<'a, T> Clone for Signal<'a, T> {
@@ -281,7 +577,7 @@ impl<'a, T> PartialEq for Signal<'a, T> {
 
 impl<'a, T> Eq for Signal<'a, T> {}
 
-pub struct StreamSignal<'a, T: Data + 'a>(Signal<'a, Option<T>>);
+pub struct StreamSignal<'a, T: Data + 'a>(Signal<'a, StreamEvent<T>>);
 
 impl<'a, T: Data + 'a> Clone for StreamSignal<'a, T> {
     fn clone(&self) -> Self {
@@ -298,27 +594,31 @@ impl<'a, T: Data + 'a> PartialEq for StreamSignal<'a, T> {
 impl<'a, T: Data + 'a> Eq for StreamSignal<'a, T> {}
 
 impl<'a, T: Data + 'a> StreamSignal<'a, T> {
-    pub fn new(signal: Signal<'a, Option<T>>) -> Self {
+    pub fn new(signal: Signal<'a, StreamEvent<T>>) -> Self {
         StreamSignal(signal)
     }
 
-    pub fn create<S: ComputationCore<ComputationResult = Option<T>> + 'a>(r: S) -> Self {
+    pub fn create<S: ComputationCore<ComputationResult = StreamEvent<T>> + 'a>(r: S) -> Self {
         Self(Signal::create(r))
     }
 
-    pub fn signal(&self) -> &Signal<'a, Option<T>> {
+    pub fn signal(&self) -> &Signal<'a, StreamEvent<T>> {
         &self.0
     }
 
-    pub fn to_signal(self) -> Signal<'a, Option<T>> {
+    pub fn to_signal(self) -> Signal<'a, StreamEvent<T>> {
         self.0
     }
 }
 
-impl<'a, T: Data + 'a> Signal<'a, Option<T>> {
+impl<'a, T: Data + 'a> Signal<'a, StreamEvent<T>> {
     pub fn collect(&self) -> Collector<'a, T> {
         Collector::new(StreamReader::new(self.clone()))
     }
+
+    pub fn collect_ring(&self, max_size: usize) -> Collector<'a, T> {
+        Collector::ring(StreamReader::new(self.clone()), max_size)
+    }
 }
 
 impl<'a, T: Data + 'a> StreamSignal<'a, T> {
@@ -330,6 +630,21 @@ impl<'a, T: Data + 'a> StreamSignal<'a, T> {
         )))
     }
 
+    pub fn map_bounded<R: Data + 'a, M: Fn(T) -> R + 'a>(
+        &self,
+        mapper: M,
+        capacity: usize,
+        policy: OverflowPolicy<R>,
+    ) -> StreamSignal<'a, R> {
+        StreamSignal::create(streams::mapper::Mapper::bounded(
+            self.signal().world(),
+            self.signal().clone(),
+            mapper,
+            capacity,
+            policy,
+        ))
+    }
+
     pub fn count(&self) -> ValueSignal<'a, u64> {
         ValueSignal::create(Counter::new(self.signal().world(), self.clone()))
     }
@@ -338,6 +653,63 @@ impl<'a, T: Data + 'a> StreamSignal<'a, T> {
         ValueSignal::create(Last::new(self.signal().world(), self.clone(), initial))
     }
 
+    pub fn group_by<K: Data + Hash + Eq + 'a, F: Fn(&T) -> K + 'a>(
+        &self,
+        key_func: F,
+    ) -> GroupedStream<'a, T, K> {
+        let world = self.signal().world();
+        GroupedStream {
+            router: Rc::new(RefCell::new(Router::new(
+                world.clone(),
+                self.clone(),
+                key_func,
+            ))),
+            world,
+        }
+    }
+
+    pub fn partition<P: Fn(&T) -> bool + 'a>(
+        &self,
+        predicate: P,
+    ) -> (StreamSignal<'a, T>, StreamSignal<'a, T>) {
+        let groups = self.group_by(predicate);
+        (groups.group(true), groups.group(false))
+    }
+
+    pub fn flat_map_latest<R: Data + 'a, F: Fn(T) -> StreamSignal<'a, R> + 'a>(
+        &self,
+        f: F,
+    ) -> StreamSignal<'a, R> {
+        self.map(f).switch_latest()
+    }
+
+    pub fn hold(&self, initial: T) -> ValueSignal<'a, T> {
+        ValueSignal::create(streams::hold::Hold::new(
+            self.signal().world(),
+            self.clone(),
+            initial,
+        ))
+    }
+
+    pub fn with_latest_from<V: Data + 'a>(
+        &self,
+        value: &ValueSignal<'a, V>,
+    ) -> StreamSignal<'a, (T, V)> {
+        StreamSignal::create(streams::with_latest::WithLatest::new(
+            self.signal().world(),
+            self.clone(),
+            value.clone(),
+        ))
+    }
+
+    pub fn gate(&self, open: &ValueSignal<'a, bool>) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::gate::Gate::new(
+            self.signal().world(),
+            self.clone(),
+            open.clone(),
+        ))
+    }
+
     pub fn filter<F: Fn(&T) -> bool + 'a>(&self, filter: F) -> StreamSignal<'a, T> {
         StreamSignal::new(Signal::create(streams::filter::Filter::new(
             self.signal().world(),
@@ -353,10 +725,48 @@ impl<'a, T: Data + 'a> StreamSignal<'a, T> {
         )))
     }
 
+    pub fn cached_bounded(
+        &self,
+        capacity: usize,
+        policy: OverflowPolicy<T>,
+    ) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::cached::Cached::bounded(
+            self.signal().world(),
+            self.clone(),
+            capacity,
+            policy,
+        ))
+    }
+
+    pub fn bounded(&self, capacity: usize, policy: OverflowPolicy<T>) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::bounded::Bounded::new(
+            self.signal().world(),
+            self.clone(),
+            capacity,
+            policy,
+        ))
+    }
+
+    pub fn replay(&self, count: usize) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::replay::Replay::new(
+            self.signal().world(),
+            self.clone(),
+            count,
+        ))
+    }
+
+    pub fn share_latest(&self) -> StreamSignal<'a, T> {
+        self.replay(1)
+    }
+
     pub fn collect(&self) -> Collector<'a, T> {
         self.signal().collect()
     }
 
+    pub fn collect_ring(&self, max_size: usize) -> Collector<'a, T> {
+        self.signal().collect_ring(max_size)
+    }
+
     pub fn filter_map<O: Data + 'a, F: Fn(T) -> Option<O> + 'a>(
         &self,
         fmap: F,
@@ -380,103 +790,351 @@ impl<'a, T: Data + 'a> StreamSignal<'a, T> {
             folder,
         ))
     }
-}
 
-pub struct ValueSignal<'a, T: Data + 'a>(Signal<'a, SingleComputationResult<T>>);
+    pub fn scan<S: Data + 'a, F: Fn(S, T) -> S + 'a>(
+        &self,
+        initial: S,
+        scanner: F,
+    ) -> StreamSignal<'a, S> {
+        StreamSignal::create(streams::scanner::Scanner::new(
+            self.signal().world(),
+            self.clone(),
+            initial,
+            scanner,
+        ))
+    }
 
-impl<'a, T: Data + 'a> Clone for ValueSignal<'a, T> {
-    fn clone(&self) -> Self {
-        Self(self.0.clone())
+    pub fn take(&self, count: usize) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::take::Take::new(
+            self.signal().world(),
+            self.clone(),
+            count,
+        ))
     }
-}
 
-impl<'a, T: Data + 'a> PartialEq for ValueSignal<'a, T> {
-    fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
+    pub fn skip(&self, count: usize) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::skip::Skip::new(
+            self.signal().world(),
+            self.clone(),
+            count,
+        ))
     }
-}
 
-impl<'a, T: Data + 'a> Eq for ValueSignal<'a, T> {}
+    pub fn take_while<P: Fn(&T) -> bool + 'a>(&self, predicate: P) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::take_while::TakeWhile::new(
+            self.signal().world(),
+            self.clone(),
+            predicate,
+        ))
+    }
 
-impl<'a, T: Data + 'a> ValueSignal<'a, T> {
-    pub fn new(signal: Signal<'a, SingleComputationResult<T>>) -> Self {
-        Self(signal)
+    pub fn skip_while<P: Fn(&T) -> bool + 'a>(&self, predicate: P) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::skip_while::SkipWhile::new(
+            self.signal().world(),
+            self.clone(),
+            predicate,
+        ))
     }
 
-    pub fn create<S: ComputationCore<ComputationResult = SingleComputationResult<T>> + 'a>(
-        r: S,
-    ) -> Self {
-        Self(Signal::create(r))
+    pub fn enumerate(&self) -> StreamSignal<'a, (usize, T)> {
+        StreamSignal::create(streams::enumerate::Enumerate::new(
+            self.signal().world(),
+            self.clone(),
+        ))
     }
 
-    pub fn signal(&self) -> &Signal<'a, SingleComputationResult<T>> {
-        &self.0
+    pub fn chunks(&self, size: usize) -> StreamSignal<'a, Vec<T>> {
+        StreamSignal::create(streams::chunks::Chunks::new(
+            self.signal().world(),
+            self.clone(),
+            size,
+        ))
     }
 
-    pub fn to_signal(self) -> Signal<'a, SingleComputationResult<T>> {
-        self.0
+    pub fn windows(&self, size: usize) -> StreamSignal<'a, Vec<T>> {
+        StreamSignal::create(streams::windows::Windows::new(
+            self.signal().world(),
+            self.clone(),
+            size,
+        ))
     }
 
-    pub fn map<R: Data + 'a, M: Fn(T) -> R + 'a>(&self, mapper: M) -> ValueSignal<'a, R> {
-        ValueSignal::create(Mapper::new(self.signal().world(), self.clone(), mapper))
+    pub fn pairwise(&self) -> StreamSignal<'a, (T, T)> {
+        StreamSignal::create(streams::pairwise::Pairwise::new(
+            self.signal().world(),
+            self.clone(),
+        ))
     }
 
-    pub fn filter<F: Fn(&T) -> bool + 'a>(&self, filter: F, initial: T) -> ValueSignal<'a, T> {
-        ValueSignal::create(Filter::new(
+    pub fn buffer_until(&self, trigger: &StreamSignal<'a, ()>) -> StreamSignal<'a, Vec<T>> {
+        StreamSignal::create(streams::buffer_until::BufferUntil::new(
             self.signal().world(),
             self.clone(),
-            initial,
-            filter,
+            trigger.clone(),
         ))
     }
 
-    pub fn bind<O: Data + 'a, B: Fn(T) -> ValueSignal<'a, O> + 'a>(
-        &self,
-        binder: B,
-    ) -> ValueSignal<'a, O> {
-        ValueSignal::create(Binder::new(self.signal().world(), self.clone(), binder))
+    pub fn debounce(&self, period: Duration) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::debounce::Debounce::new(
+            self.signal().world(),
+            self.clone(),
+            period,
+        ))
     }
 
-    pub fn changed(&self) -> StreamSignal<'a, T> {
-        StreamSignal::create(streams::changed::Changed::new(
+    pub fn throttle(&self, period: Duration) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::throttle::Throttle::new(
             self.signal().world(),
             self.clone(),
+            period,
         ))
     }
 
-    pub fn filter_map<O: Data + 'a, F: Fn(T) -> Option<O> + 'a>(
-        &self,
-        fmap: F,
-        initial: O,
-    ) -> ValueSignal<'a, O> {
-        ValueSignal::create(values::filter_mapper::FilterMapper::new(
+    pub fn delay(&self, period: Duration) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::delay::Delay::new(
             self.signal().world(),
             self.clone(),
-            initial,
-            fmap,
+            period,
         ))
     }
-}
 
-impl<'a, T: Data + 'a> ValueSignal<'a, ValueSignal<'a, T>> {
-    pub fn flatten(&self) -> ValueSignal<'a, T> {
-        self.bind(|v| v)
+    pub fn timeout(&self, period: Duration) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::timeout::Timeout::new(
+            self.signal().world(),
+            self.clone(),
+            period,
+        ))
     }
 }
 
-pub fn portal<'a, T: Data + 'a>(world: &World) -> (impl Fn(T), StreamSignal<'a, T>) {
-    let p = Portal::new(world.clone());
-    let signal = Rc::new(RefCell::new(p));
-    let s = signal.clone();
-    let pusher = move |v| s.borrow_mut().send(v);
-    (pusher, StreamSignal(Signal(signal)))
+#[derive(Clone)]
+pub struct GroupedStream<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> {
+    world: World,
+    router: Rc<RefCell<Router<'a, T, K>>>,
 }
 
-pub fn many<'a, T: Data + 'a>(
-    world: &World,
-    signals: Vec<StreamSignal<'a, T>>,
-) -> StreamSignal<'a, T> {
-    StreamSignal::create(Many::new(world.clone(), signals))
+impl<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> GroupedStream<'a, T, K> {
+    // Items that arrived before the group had a reader are delivered to its first reader
+    pub fn group(&self, key: K) -> StreamSignal<'a, T> {
+        StreamSignal::create(Routed::new(self.world.clone(), self.router.clone(), key))
+    }
+
+    // Emits every key the first time an item with it arrives, together with its group
+    pub fn groups(&self) -> StreamSignal<'a, (K, StreamSignal<'a, T>)> {
+        let grouped = self.clone();
+        StreamSignal::create(RoutedKeys::new(self.world.clone(), self.router.clone()))
+            .map(move |key| (key.cheap_clone(), grouped.group(key)))
+    }
+}
+
+impl<'a, T: Data + 'a> StreamSignal<'a, StreamSignal<'a, T>> {
+    pub fn switch_latest(&self) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::switcher::Switcher::new(
+            self.signal().world(),
+            self.clone(),
+        ))
+    }
+
+    pub fn merge_all(&self) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::merger::Merger::new(
+            self.signal().world(),
+            self.clone(),
+        ))
+    }
+
+    pub fn concat_all(&self) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::concatenator::Concatenator::new(
+            self.signal().world(),
+            self.clone(),
+        ))
+    }
+}
+
+pub struct ValueSignal<'a, T: Data + 'a>(Signal<'a, SingleComputationResult<T>>);
+
+impl<'a, T: Data + 'a> Clone for ValueSignal<'a, T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<'a, T: Data + 'a> PartialEq for ValueSignal<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<'a, T: Data + 'a> Eq for ValueSignal<'a, T> {}
+
+// A value signal that can also be written to, writes go to the mutable state it was created from
+pub struct WritableSignal<'a, T: Data + 'a> {
+    signal: ValueSignal<'a, T>,
+    writer: Rc<RefCell<dyn Writable<T> + 'a>>,
+}
+
+impl<'a, T: Data + 'a> Clone for WritableSignal<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            signal: self.signal.clone(),
+            writer: self.writer.clone(),
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> std::ops::Deref for WritableSignal<'a, T> {
+    type Target = ValueSignal<'a, T>;
+
+    fn deref(&self) -> &ValueSignal<'a, T> {
+        &self.signal
+    }
+}
+
+impl<'a, T: Data + 'a> From<WritableSignal<'a, T>> for ValueSignal<'a, T> {
+    fn from(writable: WritableSignal<'a, T>) -> Self {
+        writable.signal
+    }
+}
+
+impl<'a, T: Data + 'a> WritableSignal<'a, T> {
+    pub fn new(signal: ValueSignal<'a, T>, writer: Rc<RefCell<dyn Writable<T> + 'a>>) -> Self {
+        Self { signal, writer }
+    }
+
+    pub fn write(&self, value: T) {
+        self.writer.borrow_mut().write(value)
+    }
+
+    pub fn setter(&self) -> impl Fn(T) + 'a {
+        let writer = self.writer.clone();
+        move |value| writer.borrow_mut().write(value)
+    }
+
+    pub fn focus<U: Data + 'a, G: Fn(&T) -> U + 'a, S: Fn(&mut T, U) + 'a>(
+        &self,
+        get: G,
+        set: S,
+    ) -> WritableSignal<'a, U> {
+        let focus = Rc::new(RefCell::new(values::focus::Focus::new(
+            self.signal().world(),
+            self.signal.clone(),
+            self.writer.clone(),
+            get,
+            set,
+        )));
+        WritableSignal::new(ValueSignal::new(Signal(focus.clone())), focus)
+    }
+
+    pub fn focus_lens<U: Data + 'a>(&self, lens: Lens<T, U>) -> WritableSignal<'a, U> {
+        self.focus(lens.get, lens.set)
+    }
+}
+
+impl<'a, T: Data + 'a> ValueSignal<'a, T> {
+    pub fn new(signal: Signal<'a, SingleComputationResult<T>>) -> Self {
+        Self(signal)
+    }
+
+    pub fn create<S: ComputationCore<ComputationResult = SingleComputationResult<T>> + 'a>(
+        r: S,
+    ) -> Self {
+        Self(Signal::create(r))
+    }
+
+    pub fn signal(&self) -> &Signal<'a, SingleComputationResult<T>> {
+        &self.0
+    }
+
+    pub fn to_signal(self) -> Signal<'a, SingleComputationResult<T>> {
+        self.0
+    }
+
+    pub fn map<R: Data + 'a, M: Fn(T) -> R + 'a>(&self, mapper: M) -> ValueSignal<'a, R> {
+        ValueSignal::create(Mapper::new(self.signal().world(), self.clone(), mapper))
+    }
+
+    pub fn filter<F: Fn(&T) -> bool + 'a>(&self, filter: F, initial: T) -> ValueSignal<'a, T> {
+        ValueSignal::create(Filter::new(
+            self.signal().world(),
+            self.clone(),
+            initial,
+            filter,
+        ))
+    }
+
+    pub fn bind<O: Data + 'a, B: Fn(T) -> ValueSignal<'a, O> + 'a>(
+        &self,
+        binder: B,
+    ) -> ValueSignal<'a, O> {
+        ValueSignal::create(Binder::new(self.signal().world(), self.clone(), binder))
+    }
+
+    pub fn sample_on<U: Data + 'a>(&self, trigger: &StreamSignal<'a, U>) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::sampler::Sampler::new(
+            self.signal().world(),
+            self.clone(),
+            trigger.clone(),
+        ))
+    }
+
+    pub fn changed(&self) -> StreamSignal<'a, T> {
+        StreamSignal::create(streams::changed::Changed::new(
+            self.signal().world(),
+            self.clone(),
+        ))
+    }
+
+    pub fn filter_map<O: Data + 'a, F: Fn(T) -> Option<O> + 'a>(
+        &self,
+        fmap: F,
+        initial: O,
+    ) -> ValueSignal<'a, O> {
+        ValueSignal::create(values::filter_mapper::FilterMapper::new(
+            self.signal().world(),
+            self.clone(),
+            initial,
+            fmap,
+        ))
+    }
+}
+
+impl<'a, T: Data + 'a> ValueSignal<'a, ValueSignal<'a, T>> {
+    pub fn flatten(&self) -> ValueSignal<'a, T> {
+        self.bind(|v| v)
+    }
+}
+
+pub fn portal<'a, T: Data + 'a>(world: &World) -> (impl Fn(T), StreamSignal<'a, T>) {
+    let p = Portal::new(world.clone());
+    let signal = Rc::new(RefCell::new(p));
+    let s = signal.clone();
+    let pusher = move |v| s.borrow_mut().send(v);
+    (pusher, StreamSignal(Signal(signal)))
+}
+
+pub fn portal_bounded<'a, T: Data + 'a>(
+    world: &World,
+    capacity: usize,
+    policy: OverflowPolicy<T>,
+) -> (
+    impl Fn(T) -> Result<(), BufferOverflow<T>>,
+    StreamSignal<'a, T>,
+) {
+    let p = Portal::bounded(world.clone(), capacity, policy);
+    let signal = Rc::new(RefCell::new(p));
+    let s = signal.clone();
+    let pusher = move |v| s.borrow_mut().try_send(v);
+    (pusher, StreamSignal(Signal(signal)))
+}
+
+pub fn many<'a, T: Data + 'a>(
+    world: &World,
+    signals: Vec<StreamSignal<'a, T>>,
+) -> StreamSignal<'a, T> {
+    StreamSignal::create(Many::new(world.clone(), signals))
+}
+
+pub fn interval<'a>(world: &World, period: Duration) -> StreamSignal<'a, u64> {
+    StreamSignal::create(streams::interval::Interval::new(world.clone(), period))
 }
 
 pub fn iter_as_stream<'a, T: Data + 'a, I: Iterator<Item = T> + 'a>(
@@ -486,12 +1144,23 @@ pub fn iter_as_stream<'a, T: Data + 'a, I: Iterator<Item = T> + 'a>(
     StreamSignal::create(FromIter::new(world.clone(), iter))
 }
 
-pub fn mutable<'a, T: Data + 'a>(world: &World, initial: T) -> (impl Fn(T), ValueSignal<'a, T>) {
-    let m = Mutable::new(world.clone(), initial);
-    let signal = Rc::new(RefCell::new(m));
-    let s = signal.clone();
+// The returned signal can also be written to and focused on parts of the value
+pub fn mutable<'a, T: Data + 'a>(world: &World, initial: T) -> (impl Fn(T), WritableSignal<'a, T>) {
+    let m = Rc::new(RefCell::new(Mutable::new(world.clone(), initial)));
+    let s = m.clone();
     let mutator = move |v| s.borrow_mut().set(v);
-    (mutator, ValueSignal::new(Signal(signal)))
+    (
+        mutator,
+        WritableSignal::new(ValueSignal::new(Signal(m.clone())), m),
+    )
+}
+
+// Tracks the signals read through the context and only reevaluates once one of them changed
+pub fn computed<'a, T: Data + 'a, F: Fn(&mut ComputeContext<'_, 'a>) -> T + 'a>(
+    world: &World,
+    compute: F,
+) -> ValueSignal<'a, T> {
+    ValueSignal::create(Computed::new(world.clone(), compute))
 }
 
 pub fn constant<'a, T: Data + 'a>(world: &World, value: T) -> ValueSignal<'a, T> {
@@ -595,6 +1264,10 @@ impl NodeState {
     pub fn mark_dirty(&self, cause: DirtyingCause) {
         self.0.mark_dirty(self.1, cause)
     }
+
+    pub fn schedule(&self, at: Duration) {
+        self.0.schedule(self.1, at)
+    }
 }
 
 impl Drop for NodeState {
@@ -627,10 +1300,14 @@ impl<'a, T: 'a, Res, R: Reader<'a, T, Result = Res>> ParentSignal<'a, T, Res, R>
             parent: signal,
             own_index,
             reader,
-            pd: PhantomData::default(),
+            pd: PhantomData,
         }
     }
 
+    pub fn parent(&self) -> &Signal<'a, T> {
+        &self.parent
+    }
+
     pub fn set_parent(&mut self, signal: Signal<'a, T>) {
         self.parent.remove_dependency(self.own_index);
         signal.add_dependency(self.own_index);
@@ -650,7 +1327,21 @@ impl<'a, T: 'a, Res, R: Reader<'a, T, Result = Res>> Drop for ParentSignal<'a, T
     }
 }
 
-pub type ParentStreamSignal<'a, T> = ParentSignal<'a, Option<T>, Option<T>, StreamReader<'a, T>>;
+pub type ParentStreamSignal<'a, T> =
+    ParentSignal<'a, StreamEvent<T>, StreamEvent<T>, StreamReader<'a, T>>;
+
+impl<'a, T: Data + 'a> ParentStreamSignal<'a, T> {
+    //Feeds all available items to f and returns whether the source has completed
+    pub fn drain_items<F: FnMut(T)>(&mut self, mut f: F) -> bool {
+        loop {
+            match self.compute() {
+                StreamEvent::Item(t) => f(t),
+                StreamEvent::Pending => return false,
+                StreamEvent::Complete => return true,
+            }
+        }
+    }
+}
 
 pub type ParentValueSignal<'a, T> =
     ParentSignal<'a, SingleComputationResult<T>, SingleComputationResult<T>, ChangeReader<'a, T>>;
@@ -658,25 +1349,64 @@ pub type ParentValueSignal<'a, T> =
 pub type ParentCachedValueSignal<'a, T> =
     ParentSignal<'a, SingleComputationResult<T>, (bool, T), CachedReader<'a, T>>;
 
+type ItemCallback<'a, T> = Box<dyn FnMut(&T) + 'a>;
+
 pub struct Collector<'a, T: Data + 'a> {
     reader: StreamReader<'a, T>,
-    items: Vec<T>,
+    items: VecDeque<T>,
+    max_size: Option<usize>,
+    callbacks: Vec<ItemCallback<'a, T>>,
+    complete: bool,
 }
 
 impl<'a, T: Data + 'a> Collector<'a, T> {
     pub fn new(reader: StreamReader<'a, T>) -> Self {
         Self {
             reader,
-            items: Vec::new(),
+            items: VecDeque::new(),
+            max_size: None,
+            callbacks: Vec::new(),
+            complete: false,
+        }
+    }
+
+    // Only keeps the newest `max_size` items, older ones are dropped on update
+    pub fn ring(reader: StreamReader<'a, T>, max_size: usize) -> Self {
+        assert!(max_size > 0, "Collector size must be at least 1");
+        Self {
+            max_size: Some(max_size),
+            ..Self::new(reader)
         }
     }
 
+    // Callbacks see every item during update, including the ones a ring collector drops
+    pub fn on_item<F: FnMut(&T) + 'a>(&mut self, callback: F) {
+        self.callbacks.push(Box::new(callback));
+    }
+
     pub fn update(&mut self) {
-        while let Some(next) = self.reader.read() {
-            self.items.push(next);
+        loop {
+            match self.reader.read() {
+                StreamEvent::Item(next) => {
+                    self.callbacks.iter_mut().for_each(|cb| cb(&next));
+                    if self.max_size == Some(self.items.len()) {
+                        self.items.pop_front();
+                    }
+                    self.items.push_back(next)
+                }
+                StreamEvent::Pending => break,
+                StreamEvent::Complete => {
+                    self.complete = true;
+                    break;
+                }
+            }
         }
     }
 
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
     pub fn clear(&mut self) {
         self.items.clear();
     }
@@ -684,6 +1414,28 @@ impl<'a, T: Data + 'a> Collector<'a, T> {
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.items.iter()
     }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.items.drain(..)
+    }
+
+    // Returns up to `n` of the newest items in order and discards everything else
+    pub fn take_latest(&mut self, n: usize) -> Vec<T> {
+        let skip = self.items.len().saturating_sub(n);
+        self.items.drain(..).skip(skip).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn lagged(&mut self) -> usize {
+        self.reader.lagged()
+    }
 }
 
 #[cfg(test)]
@@ -720,6 +1472,118 @@ mod tests {
         assert_eq!(3, read_once(&mapped));
     }
 
+    #[test]
+    fn test_focus() {
+        let world = World::new();
+        let (_set, pair) = mutable(&world, (1, 2));
+        let first = pair.focus(|p| p.0, |p, v| p.0 = v);
+        let second = pair.focus(|p| p.1, |p, v| p.1 = v);
+        let set_first = first.setter();
+        set_first(5);
+        assert_eq!(read_once(&pair), (5, 2));
+        assert_eq!(read_once(&first), 5);
+        second.write(3);
+        assert_eq!(read_once(&pair), (5, 3));
+        pair.write((0, 0));
+        assert_eq!(read_once(&second), 0);
+    }
+
+    #[test]
+    fn test_computed_dynamic_dependencies() {
+        let world = World::new();
+        let (set_flag, flag) = mutable(&world, true);
+        let (set_a, a) = mutable(&world, 1);
+        let (set_b, b) = mutable(&world, 10);
+        let runs = Rc::new(RefCell::new(0));
+        let r = runs.clone();
+        let result = computed(&world, move |cx| {
+            *r.borrow_mut() += 1;
+            if cx.get(&flag) {
+                cx.get(&a)
+            } else {
+                cx.get(&b)
+            }
+        });
+        assert_eq!(read_once(&result), 1);
+        set_b(20);
+        assert_eq!(read_once(&result), 1);
+        assert_eq!(*runs.borrow(), 1);
+        set_flag(false);
+        assert_eq!(read_once(&result), 20);
+        set_a(2);
+        assert_eq!(read_once(&result), 20);
+        assert_eq!(*runs.borrow(), 2);
+        set_b(30);
+        assert_eq!(read_once(&result), 30);
+        assert_eq!(*runs.borrow(), 3);
+    }
+
+    #[test]
+    fn test_computed_unchanged_inputs() {
+        let world = World::new();
+        let (set, v) = mutable(&world, 1);
+        let parity = v.map(|x| x % 2);
+        let runs = Rc::new(RefCell::new(0));
+        let r = runs.clone();
+        let result = computed(&world, move |cx| {
+            *r.borrow_mut() += 1;
+            cx.get(&parity) * 100
+        });
+        set(3);
+        assert_eq!(read_once(&result), 100);
+        assert_eq!(*runs.borrow(), 1);
+        set(4);
+        assert_eq!(read_once(&result), 0);
+        assert_eq!(*runs.borrow(), 2);
+    }
+
+    struct Maximum<'a> {
+        source: ParentStreamSignal<'a, i32>,
+        store: crate::stores::SingleValueStore<i32>,
+        node: NodeState,
+    }
+
+    impl<'a> Operator for Maximum<'a> {
+        type Output = SingleComputationResult<i32>;
+        type Store = crate::stores::SingleValueStore<i32>;
+
+        fn node_state(&self) -> &NodeState {
+            &self.node
+        }
+
+        fn store(&mut self) -> &mut Self::Store {
+            &mut self.store
+        }
+
+        fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+            if self.is_dirty() {
+                self.node.clean();
+                let store = &mut self.store;
+                self.source
+                    .drain_items(|i| store.set_value(i.max(store.get())));
+            }
+            self.store.read(reader)
+        }
+    }
+
+    #[test]
+    fn test_custom_operator() {
+        let world = World::new();
+        let (send, stream) = portal(&world);
+        let node = NodeState::new(world.clone());
+        let max = ValueSignal::create(Maximum {
+            source: ParentSignal::new(stream.to_signal(), node.node()),
+            store: crate::stores::SingleValueStore::new(0),
+            node,
+        });
+        let doubled = max.map(|m| m * 2);
+        send(3);
+        send(1);
+        assert_eq!(read_once(&doubled), 6);
+        send(5);
+        assert_eq!(read_once(&doubled), 10);
+    }
+
     #[test]
     fn test_map2_constant() {
         let world = World::new();
@@ -771,7 +1635,13 @@ mod tests {
         let (set1, v1) = mutable(&world, 1);
         let (set2, v2) = mutable(&world, 2);
         let (switch, switcher) = mutable(&world, false);
-        let b = switcher.bind(move |b| if b { v1.clone() } else { v2.clone() });
+        let b = switcher.bind(move |b| {
+            if b {
+                ValueSignal::clone(&v1)
+            } else {
+                ValueSignal::clone(&v2)
+            }
+        });
         assert_eq!(2, read_once(&b));
         switch(true);
         assert_eq!(1, read_once(&b));
@@ -792,17 +1662,13 @@ mod tests {
         let (set2, v2) = mutable(&world, 2);
         let (set_sw1, sw1) = mutable(&world, false);
         let (set_sw2, sw2) = mutable(&world, false);
-        let res = bind2(
-            &sw1,
-            &sw2,
-            move |b1, b2| {
-                if b1 && b2 {
-                    v1.clone()
-                } else {
-                    v2.clone()
-                }
-            },
-        );
+        let res = bind2(&sw1, &sw2, move |b1, b2| {
+            if b1 && b2 {
+                ValueSignal::clone(&v1)
+            } else {
+                ValueSignal::clone(&v2)
+            }
+        });
         assert_eq!(2, read_once(&res));
         set2(4);
         assert_eq!(4, read_once(&res));
@@ -838,7 +1704,7 @@ mod tests {
     fn collect_all<T: Data>(coll: &mut Collector<T>) -> Vec<T> {
         coll.clear();
         coll.update();
-        collect_current(&coll)
+        collect_current(coll)
     }
 
     #[test]
@@ -859,6 +1725,44 @@ mod tests {
         assert_eq!(collect_current(&collector), vec![2, 3, 4]);
     }
 
+    #[test]
+    fn test_collector_drain_take_latest() {
+        let world = World::new();
+        let (send, s1) = portal(&world);
+        let mut collector = s1.collect();
+        assert!(collector.is_empty());
+        send(1);
+        send(2);
+        collector.update();
+        assert_eq!(collector.len(), 2);
+        assert_eq!(collector.drain().collect::<Vec<_>>(), vec![1, 2]);
+        assert!(collector.is_empty());
+        (3..8).for_each(&send);
+        collector.update();
+        assert_eq!(collector.take_latest(2), vec![6, 7]);
+        assert!(collector.is_empty());
+        send(8);
+        collector.update();
+        assert_eq!(collector.take_latest(5), vec![8]);
+    }
+
+    #[test]
+    fn test_collector_ring_and_on_item() {
+        let world = World::new();
+        let (send, s1) = portal(&world);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut collector = s1.collect_ring(2);
+        let s = seen.clone();
+        collector.on_item(move |i| s.borrow_mut().push(*i));
+        (1..5).for_each(&send);
+        collector.update();
+        assert_eq!(collect_current(&collector), vec![3, 4]);
+        assert_eq!(*seen.borrow(), vec![1, 2, 3, 4]);
+        send(5);
+        collector.update();
+        assert_eq!(collect_current(&collector), vec![4, 5]);
+    }
+
     #[test]
     fn test_stream_map() {
         let world = World::new();
@@ -1066,4 +1970,638 @@ mod tests {
         send(3);
         assert_eq!(3, read_once(&v));
     }
+
+    #[test]
+    fn test_buffered_store_shared() {
+        use crate::stores::{BufferedStore, Store};
+        let mut store = BufferedStore::new();
+        store.push(0);
+        assert_eq!(store.buffered(), 0);
+        let r1 = store.create_reader();
+        let r2 = store.create_reader();
+        store.push(1);
+        store.push(2);
+        assert_eq!(store.read(r1), StreamEvent::Item(1));
+        assert_eq!(store.buffered(), 2);
+        let r3 = store.create_reader();
+        store.push(3);
+        assert_eq!(store.read(r2), StreamEvent::Item(1));
+        assert_eq!(store.buffered(), 2);
+        assert_eq!(store.read(r3), StreamEvent::Item(3));
+        assert_eq!(store.read(r3), StreamEvent::Pending);
+        store.destroy_reader(r2);
+        assert_eq!(store.buffered(), 2);
+        assert_eq!(store.read(r1), StreamEvent::Item(2));
+        assert_eq!(store.read(r1), StreamEvent::Item(3));
+        assert_eq!(store.read(r1), StreamEvent::Pending);
+        assert_eq!(store.buffered(), 0);
+    }
+
+    #[test]
+    fn test_stream_many_readers() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let mut c1 = s.collect();
+        let mut c2 = s.collect();
+        send(1);
+        send(2);
+        assert_eq!(collect_all(&mut c1), vec![1, 2]);
+        send(3);
+        assert_eq!(collect_all(&mut c2), vec![1, 2, 3]);
+        assert_eq!(collect_all(&mut c1), vec![3]);
+    }
+
+    #[test]
+    fn test_bounded_drop_oldest() {
+        let world = World::new();
+        let (send, s) = portal_bounded(&world, 2, OverflowPolicy::DropOldest);
+        let mut slow = s.collect();
+        let mut fast = s.collect();
+        for i in 0..3 {
+            send(i).unwrap();
+            assert_eq!(collect_all(&mut fast), vec![i]);
+        }
+        assert_eq!(collect_all(&mut slow), vec![1, 2]);
+        assert_eq!(slow.lagged(), 1);
+        assert_eq!(slow.lagged(), 0);
+        assert_eq!(fast.lagged(), 0);
+    }
+
+    #[test]
+    fn test_bounded_drop_newest_and_error() {
+        let world = World::new();
+        let (send, s) = portal_bounded(&world, 2, OverflowPolicy::DropNewest);
+        let mut c = s.collect();
+        (0..4).for_each(|i| send(i).unwrap());
+        assert_eq!(collect_all(&mut c), vec![0, 1]);
+        assert_eq!(c.lagged(), 2);
+
+        let (send, s) = portal_bounded(&world, 1, OverflowPolicy::Error);
+        let mut c = s.collect();
+        assert_eq!(send(1), Ok(()));
+        assert_eq!(send(2), Err(BufferOverflow(2)));
+        assert_eq!(collect_all(&mut c), vec![1]);
+        assert_eq!(send(3), Ok(()));
+    }
+
+    #[test]
+    fn test_bounded_coalesce() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let mapped = s.map_bounded(
+            |i| i * 10,
+            2,
+            OverflowPolicy::Coalesce(Box::new(|a, b| a + b)),
+        );
+        let mut slow = mapped.collect();
+        let mut fast = mapped.collect();
+        send(1);
+        send(2);
+        assert_eq!(collect_all(&mut fast), vec![10, 20]);
+        send(3);
+        assert_eq!(collect_all(&mut fast), vec![30]);
+        send(4);
+        assert_eq!(collect_all(&mut fast), vec![40]);
+        assert_eq!(collect_all(&mut slow), vec![60, 40]);
+        assert_eq!(slow.lagged(), 0);
+    }
+
+    #[test]
+    fn test_bounded_coalesce_readers_in_every_gap() {
+        let mut store =
+            stores::BufferedStore::bounded(2, OverflowPolicy::Coalesce(Box::new(|a, b| a + b)));
+        let first = store.create_reader();
+        let second = store.create_reader();
+        let third = store.create_reader();
+        store.push(1);
+        assert_eq!(store.read(second), StreamEvent::Item(1));
+        store.push(2);
+        assert_eq!(store.read(third), StreamEvent::Item(1));
+        assert_eq!(store.read(third), StreamEvent::Item(2));
+        store.push(3);
+        assert_eq!(store.buffered(), 2);
+        assert_eq!(store.read(first), StreamEvent::Item(1));
+        assert_eq!(store.read(first), StreamEvent::Item(5));
+        assert_eq!(store.read(second), StreamEvent::Item(5));
+        assert_eq!(store.read(third), StreamEvent::Pending);
+        assert_eq!(store.take_lag(first), 0);
+        assert_eq!(store.take_lag(second), 0);
+        assert_eq!(store.take_lag(third), 1);
+    }
+
+    #[test]
+    fn test_bounded_error_on_derived_nodes() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let mut bounded = s.bounded(1, OverflowPolicy::Error).collect();
+        let mut mapped = s
+            .map_bounded(|i| i * 10, 1, OverflowPolicy::Error)
+            .collect();
+        send(1);
+        send(2);
+        assert_eq!(collect_all(&mut bounded), vec![1]);
+        assert_eq!(bounded.lagged(), 1);
+        assert_eq!(collect_all(&mut mapped), vec![10]);
+        assert_eq!(mapped.lagged(), 1);
+        send(3);
+        assert_eq!(collect_all(&mut bounded), vec![3]);
+        assert_eq!(bounded.lagged(), 0);
+    }
+
+    #[test]
+    fn test_replay() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let replayed = s.replay(2);
+        let mut early = replayed.collect();
+        send(1);
+        send(2);
+        send(3);
+        let mut late = replayed.collect();
+        assert_eq!(collect_all(&mut late), vec![2, 3]);
+        assert_eq!(collect_all(&mut early), vec![1, 2, 3]);
+        send(4);
+        assert_eq!(collect_all(&mut late), vec![4]);
+        assert_eq!(collect_all(&mut early), vec![4]);
+    }
+
+    #[test]
+    fn test_share_latest() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let latest = s.map(|i| i * 2).share_latest();
+        let mut before = latest.collect();
+        assert_eq!(collect_all(&mut before), Vec::<i32>::new());
+        send(1);
+        send(2);
+        assert_eq!(collect_all(&mut before), vec![2, 4]);
+        let mut after = latest.collect();
+        assert_eq!(collect_all(&mut after), vec![4]);
+        assert_eq!(collect_all(&mut after), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_iter_completion() {
+        let world = World::new();
+        let s = iter_as_stream(&world, vec![1, 2, 3].into_iter());
+        let mut c = s.map(|i| i * 2).filter(|i| *i > 2).collect();
+        assert!(!c.is_complete());
+        assert_eq!(collect_all(&mut c), vec![4, 6]);
+        assert!(c.is_complete());
+        let sum = iter_as_stream(&world, 1..4).fold(|a, b| a + b, 0);
+        assert_eq!(6, read_once(&sum));
+        assert_eq!(6, read_once(&sum));
+    }
+
+    #[test]
+    fn test_many_completion() {
+        let world = World::new();
+        let (send, open) = portal(&world);
+        let finite = iter_as_stream(&world, vec![1, 2].into_iter());
+        let mut all = many(&world, vec![finite]).collect();
+        assert_eq!(collect_all(&mut all), vec![1, 2]);
+        assert!(all.is_complete());
+
+        let mut mixed = many(&world, vec![open, iter_as_stream(&world, 0..1)]).collect();
+        send(5);
+        let mut received = collect_all(&mut mixed);
+        received.sort();
+        assert_eq!(received, vec![0, 5]);
+        assert!(!mixed.is_complete());
+    }
+
+    #[test]
+    fn test_zip_completion() {
+        let world = World::new();
+        let (send, s1) = portal(&world);
+        let s2 = iter_as_stream(&world, vec![10, 20].into_iter());
+        let mut zipped = zip_map(&s1, &s2, |a, b| a + b).collect();
+        send(1);
+        assert_eq!(collect_all(&mut zipped), vec![11]);
+        assert!(!zipped.is_complete());
+        send(2);
+        send(3);
+        assert_eq!(collect_all(&mut zipped), vec![22]);
+        assert!(zipped.is_complete());
+    }
+
+    #[test]
+    fn test_take_skip() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let mut taken = s.take(2).collect();
+        let mut skipped = s.skip(2).collect();
+        send(1);
+        assert_eq!(collect_all(&mut taken), vec![1]);
+        assert!(!taken.is_complete());
+        send(2);
+        send(3);
+        assert_eq!(collect_all(&mut taken), vec![2]);
+        assert!(taken.is_complete());
+        assert_eq!(collect_all(&mut skipped), vec![3]);
+        send(4);
+        assert_eq!(collect_all(&mut taken), Vec::<i32>::new());
+        assert_eq!(collect_all(&mut skipped), vec![4]);
+        let mut none = s.take(0).collect();
+        assert_eq!(collect_all(&mut none), Vec::<i32>::new());
+        assert!(none.is_complete());
+    }
+
+    #[test]
+    fn test_take_skip_while() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let mut taken = s.take_while(|i| *i < 3).collect();
+        let mut skipped = s.skip_while(|i| *i < 3).collect();
+        send(1);
+        send(2);
+        assert_eq!(collect_all(&mut taken), vec![1, 2]);
+        assert_eq!(collect_all(&mut skipped), Vec::<i32>::new());
+        send(3);
+        send(1);
+        assert_eq!(collect_all(&mut taken), Vec::<i32>::new());
+        assert!(taken.is_complete());
+        assert_eq!(collect_all(&mut skipped), vec![3, 1]);
+    }
+
+    #[test]
+    fn test_scan_enumerate() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let mut sums = s.scan(0, |acc, i| acc + i).collect();
+        let mut indexed = s.enumerate().collect();
+        send(1);
+        send(2);
+        send(3);
+        assert_eq!(collect_all(&mut sums), vec![1, 3, 6]);
+        assert_eq!(collect_all(&mut indexed), vec![(0, 1), (1, 2), (2, 3)]);
+        send(4);
+        assert_eq!(collect_all(&mut sums), vec![10]);
+        assert_eq!(collect_all(&mut indexed), vec![(3, 4)]);
+        let mut finite = iter_as_stream(&world, 1..4)
+            .scan(1, |acc, i| acc * i)
+            .collect();
+        assert_eq!(collect_all(&mut finite), vec![1, 2, 6]);
+        assert!(finite.is_complete());
+    }
+
+    #[test]
+    fn test_chunks_windows() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let mut chunks = s.chunks(2).collect();
+        let mut windows = s.windows(3).collect();
+        send(1);
+        send(2);
+        send(3);
+        assert_eq!(collect_all(&mut chunks), vec![vec![1, 2]]);
+        assert_eq!(collect_all(&mut windows), vec![vec![1, 2, 3]]);
+        send(4);
+        assert_eq!(collect_all(&mut chunks), vec![vec![3, 4]]);
+        assert_eq!(collect_all(&mut windows), vec![vec![2, 3, 4]]);
+        let mut finite = iter_as_stream(&world, 0..5).chunks(2).collect();
+        assert_eq!(
+            collect_all(&mut finite),
+            vec![vec![0, 1], vec![2, 3], vec![4]]
+        );
+        assert!(finite.is_complete());
+    }
+
+    #[test]
+    fn test_pairwise() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let mut deltas = s.pairwise().map(|(a, b)| b - a).collect();
+        send(1);
+        assert_eq!(collect_all(&mut deltas), Vec::<i32>::new());
+        send(4);
+        send(2);
+        assert_eq!(collect_all(&mut deltas), vec![3, -2]);
+    }
+
+    #[test]
+    fn test_buffer_until() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let (flush, trigger) = portal(&world);
+        let mut buffered = s.buffer_until(&trigger).collect();
+        send(1);
+        send(2);
+        assert_eq!(collect_all(&mut buffered), Vec::<Vec<i32>>::new());
+        flush(());
+        assert_eq!(collect_all(&mut buffered), vec![vec![1, 2]]);
+        flush(());
+        assert_eq!(collect_all(&mut buffered), vec![Vec::<i32>::new()]);
+        send(3);
+        flush(());
+        assert_eq!(collect_all(&mut buffered), vec![vec![3]]);
+    }
+
+    #[test]
+    fn test_buffer_until_flushes_per_trigger() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let (flush, trigger) = portal(&world);
+        let mut buffered = s.buffer_until(&trigger).collect();
+        send(1);
+        send(2);
+        flush(());
+        flush(());
+        send(3);
+        flush(());
+        assert_eq!(
+            collect_all(&mut buffered),
+            vec![vec![1, 2, 3], vec![], vec![]]
+        );
+    }
+
+    #[test]
+    fn test_buffer_until_trigger_completion() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let (flush, flags) = portal(&world);
+        let trigger = flags.take_while(|open| *open).map(|_| ());
+        let mut buffered = s.buffer_until(&trigger).collect();
+        send(1);
+        flush(true);
+        assert_eq!(collect_all(&mut buffered), vec![vec![1]]);
+        send(2);
+        flush(false);
+        assert_eq!(collect_all(&mut buffered), vec![vec![2]]);
+        assert!(buffered.is_complete());
+    }
+
+    fn ms(millis: u64) -> std::time::Duration {
+        std::time::Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_advance_instant_clock() {
+        let world = World::with_clock(time::InstantClock::new());
+        let (send, s) = portal(&world);
+        let mut delayed = s.delay(ms(0)).collect();
+        send(1);
+        world.advance_time(ms(10));
+        assert_eq!(collect_all(&mut delayed), vec![1]);
+    }
+
+    #[test]
+    fn test_debounce_throttle() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let mut debounced = s.debounce(ms(100)).collect();
+        let mut throttled = s.throttle(ms(100)).collect();
+        send(1);
+        assert_eq!(collect_all(&mut debounced), Vec::<i32>::new());
+        assert_eq!(collect_all(&mut throttled), vec![1]);
+        world.advance_time(ms(50));
+        send(2);
+        assert_eq!(collect_all(&mut debounced), Vec::<i32>::new());
+        assert_eq!(collect_all(&mut throttled), Vec::<i32>::new());
+        world.advance_time(ms(60));
+        assert_eq!(collect_all(&mut debounced), Vec::<i32>::new());
+        send(3);
+        assert_eq!(collect_all(&mut debounced), Vec::<i32>::new());
+        assert_eq!(collect_all(&mut throttled), vec![3]);
+        world.advance_time(ms(100));
+        assert_eq!(collect_all(&mut debounced), vec![3]);
+    }
+
+    #[test]
+    fn test_debounce_expired_before_next_item() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let mut debounced = s.debounce(ms(100)).collect();
+        send(1);
+        assert_eq!(collect_all(&mut debounced), Vec::<i32>::new());
+        world.advance_time(ms(200));
+        send(2);
+        assert_eq!(collect_all(&mut debounced), vec![1]);
+        world.advance_time(ms(100));
+        assert_eq!(collect_all(&mut debounced), vec![2]);
+    }
+
+    #[test]
+    fn test_delay_timeout() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let mut delayed = s.delay(ms(100)).collect();
+        let mut timeout = s.timeout(ms(100)).collect();
+        send(1);
+        assert_eq!(collect_all(&mut delayed), Vec::<i32>::new());
+        assert_eq!(collect_all(&mut timeout), vec![1]);
+        world.advance_time(ms(50));
+        send(2);
+        assert_eq!(collect_all(&mut timeout), vec![2]);
+        assert_eq!(collect_all(&mut delayed), Vec::<i32>::new());
+        world.advance_time(ms(50));
+        assert_eq!(collect_all(&mut delayed), vec![1]);
+        assert!(!timeout.is_complete());
+        world.advance_time(ms(100));
+        assert_eq!(collect_all(&mut delayed), vec![2]);
+        assert_eq!(collect_all(&mut timeout), Vec::<i32>::new());
+        assert!(timeout.is_complete());
+    }
+
+    #[test]
+    fn test_interval() {
+        let world = World::new();
+        let mut ticks = interval(&world, ms(10)).collect();
+        assert_eq!(collect_all(&mut ticks), Vec::<u64>::new());
+        world.advance_time(ms(10));
+        assert_eq!(collect_all(&mut ticks), vec![0]);
+        world.advance_time(ms(25));
+        assert_eq!(collect_all(&mut ticks), vec![1, 2]);
+        assert_eq!(world.now(), ms(35));
+    }
+
+    #[test]
+    fn test_sample_on() {
+        let world = World::new();
+        let (set, v) = mutable(&world, 1);
+        let (tick, trigger) = portal(&world);
+        let mut samples = v.sample_on(&trigger).collect();
+        set(2);
+        assert_eq!(collect_all(&mut samples), Vec::<i32>::new());
+        tick(());
+        tick(());
+        assert_eq!(collect_all(&mut samples), vec![2, 2]);
+        set(3);
+        tick(());
+        assert_eq!(collect_all(&mut samples), vec![3]);
+    }
+
+    #[test]
+    fn test_with_latest_from_and_gate() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let (set_factor, factor) = mutable(&world, 2);
+        let (set_open, open) = mutable(&world, false);
+        let mut scaled = s.with_latest_from(&factor).map(|(i, f)| i * f).collect();
+        let mut gated = s.gate(&open).collect();
+        send(1);
+        assert_eq!(collect_all(&mut scaled), vec![2]);
+        assert_eq!(collect_all(&mut gated), Vec::<i32>::new());
+        set_factor(10);
+        set_open(true);
+        send(2);
+        assert_eq!(collect_all(&mut scaled), vec![20]);
+        assert_eq!(collect_all(&mut gated), vec![2]);
+    }
+
+    #[test]
+    fn test_hold() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let held = s.hold(0);
+        let signal = held.signal();
+        let reader = signal.create_reader();
+        assert_eq!(SingleComputationResult::Changed(0), signal.compute(reader));
+        send(1);
+        assert_eq!(SingleComputationResult::Changed(1), signal.compute(reader));
+        send(1);
+        assert_eq!(SingleComputationResult::Changed(1), signal.compute(reader));
+        assert_eq!(SingleComputationResult::Unchanged, signal.compute(reader));
+        signal.destroy_reader(reader);
+    }
+
+    #[test]
+    fn test_switch_latest() {
+        let world = World::new();
+        let (send_mouse, mouse) = portal(&world);
+        let (send_keys, keys) = portal(&world);
+        let (select, devices) = portal(&world);
+        let mut events = devices.switch_latest().collect();
+        select(mouse.clone());
+        assert_eq!(collect_all(&mut events), Vec::<i32>::new());
+        send_mouse(1);
+        send_keys(100);
+        assert_eq!(collect_all(&mut events), vec![1]);
+        send_mouse(2);
+        select(keys.clone());
+        assert_eq!(collect_all(&mut events), vec![2]);
+        send_keys(101);
+        send_mouse(3);
+        assert_eq!(collect_all(&mut events), vec![101]);
+        let mut lengths = devices
+            .map(|_| ())
+            .flat_map_latest(|_| iter_as_stream(&world, 0..2))
+            .collect();
+        select(mouse);
+        assert_eq!(collect_all(&mut lengths), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_merge_concat_all() {
+        let world = World::new();
+        let (send1, s1) = portal(&world);
+        let (send2, s2) = portal(&world);
+        let (add, streams) = portal(&world);
+        let mut merged = streams.merge_all().collect();
+        add(s1.clone());
+        add(s2.clone());
+        assert_eq!(collect_all(&mut merged), Vec::<i32>::new());
+        send2(20);
+        send1(10);
+        let mut received = collect_all(&mut merged);
+        received.sort();
+        assert_eq!(received, vec![10, 20]);
+
+        let mut concatenated = iter_as_stream(
+            &world,
+            vec![iter_as_stream(&world, 0..2), iter_as_stream(&world, 5..7)].into_iter(),
+        )
+        .concat_all()
+        .collect();
+        assert_eq!(collect_all(&mut concatenated), vec![0, 1, 5, 6]);
+        assert!(concatenated.is_complete());
+    }
+
+    #[test]
+    fn test_partition() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let (even, odd) = s.partition(|i| i % 2 == 0);
+        let mut even = even.collect();
+        let mut odd = odd.collect();
+        (1..6).for_each(&send);
+        assert_eq!(collect_all(&mut odd), vec![1, 3, 5]);
+        assert_eq!(collect_all(&mut even), vec![2, 4]);
+        send(6);
+        assert_eq!(collect_all(&mut even), vec![6]);
+        assert_eq!(collect_all(&mut odd), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_group_by() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let calls = Rc::new(RefCell::new(0));
+        let c = calls.clone();
+        let groups = s.group_by(move |word: &&str| {
+            *c.borrow_mut() += 1;
+            word.len()
+        });
+        let mut short = groups.group(3).collect();
+        send("cat");
+        send("horse");
+        send("dog");
+        assert_eq!(collect_all(&mut short), vec!["cat", "dog"]);
+        let mut long = groups.group(5).collect();
+        send("mouse");
+        assert_eq!(collect_all(&mut long), vec!["horse", "mouse"]);
+        assert_eq!(collect_all(&mut short), Vec::<&str>::new());
+        assert_eq!(*calls.borrow(), 4);
+    }
+
+    #[test]
+    fn test_group_by_item_before_group() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let groups = s.group_by(|i: &i32| i % 3);
+        let mut discovered = groups.groups().collect();
+        let mut zero = groups.group(0).collect();
+        send(1);
+        send(3);
+        send(4);
+        assert_eq!(collect_all(&mut zero), vec![3]);
+        discovered.update();
+        let mut new_groups = discovered.drain().collect::<Vec<_>>();
+        assert_eq!(
+            new_groups.iter().map(|(k, _)| *k).collect::<Vec<_>>(),
+            vec![1, 0]
+        );
+        let mut one = new_groups.remove(0).1.collect();
+        assert_eq!(collect_all(&mut one), vec![1, 4]);
+        send(7);
+        send(5);
+        assert_eq!(collect_all(&mut one), vec![7]);
+        let mut two = groups.group(2).collect();
+        assert_eq!(collect_all(&mut two), vec![5]);
+    }
+
+    #[test]
+    fn test_partition_one_side_unread() {
+        let world = World::new();
+        let (send, s) = portal(&world);
+        let (evens, _odds) = s.partition(|i: &usize| i.is_multiple_of(2));
+        let groups = s.group_by(|i: &usize| i.is_multiple_of(2));
+        let mut evens = evens.collect();
+        let odds = groups.group(false);
+        (0..streams::router::PENDING_LIMIT * 2 + 2).for_each(&send);
+        assert_eq!(
+            collect_all(&mut evens).len(),
+            streams::router::PENDING_LIMIT + 1
+        );
+        // Only the newest items are kept for a key without a reader
+        let mut odds = odds.collect();
+        let odd_items = collect_all(&mut odds);
+        assert_eq!(odd_items.len(), streams::router::PENDING_LIMIT);
+        assert_eq!(odd_items[0], 3);
+        // Items that arrive after the last reader is gone are buffered again
+        drop(odds);
+        send(1);
+        let mut odds = groups.group(false).collect();
+        assert_eq!(collect_all(&mut odds), vec![1]);
+    }
 }
@@ -64,7 +64,7 @@ impl<'a, T: Data + 'a> Drop for CachedReader<'a, T> {
 }
 
 pub struct StreamReader<'a, T: Data + 'a> {
-    signal: Signal<'a, Option<T>>,
+    signal: Signal<'a, StreamEvent<T>>,
     token: ReaderToken,
 }
 
@@ -74,12 +74,12 @@ impl<'a, T: Data + 'a> Iterator for StreamReaderIter<'a, T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.read()
+        self.0.read().item()
     }
 }
 
-impl<'a, T: Data + 'a> Reader<'a, Option<T>> for StreamReader<'a, T> {
-    type Result = Option<T>;
+impl<'a, T: Data + 'a> Reader<'a, StreamEvent<T>> for StreamReader<'a, T> {
+    type Result = StreamEvent<T>;
 
     fn new(signal: Signal<'a, Self::Result>) -> Self {
         let token = signal.create_reader();
@@ -91,6 +91,12 @@ impl<'a, T: Data + 'a> Reader<'a, Option<T>> for StreamReader<'a, T> {
     }
 }
 
+impl<'a, T: Data + 'a> StreamReader<'a, T> {
+    pub fn lagged(&mut self) -> usize {
+        self.signal.take_lag(self.token)
+    }
+}
+
 impl<'a, T: Data + 'a> IntoIterator for StreamReader<'a, T> {
     type Item = T;
     type IntoIter = StreamReaderIter<'a, T>;
@@ -0,0 +1,128 @@
+use crate::collections::{CollectionSignal, SetChange};
+use crate::readers::{Reader, StreamReader};
+use crate::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::{self, BufRead, Write};
+
+// Writes every item of a stream in bincode, one after another, so it can later be fed back with replay_into
+pub struct Recorder<'a, T: Data + Serialize + 'a, W: Write> {
+    reader: StreamReader<'a, T>,
+    writer: W,
+}
+
+impl<'a, T: Data + Serialize + 'a, W: Write> Recorder<'a, T, W> {
+    pub fn new(reader: StreamReader<'a, T>, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    // Writes all items that arrived since the last update and returns how many there were
+    pub fn update(&mut self) -> io::Result<usize> {
+        let mut written = 0;
+        while let StreamEvent::Item(item) = self.reader.read() {
+            bincode::serialize_into(&mut self.writer, &item).map_err(|e| into_io_error(*e))?;
+            written += 1;
+        }
+        self.writer.flush()?;
+        Ok(written)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<'a, T: Data + Serialize + 'a> StreamSignal<'a, T> {
+    pub fn record<W: Write>(&self, writer: W) -> Recorder<'a, T, W> {
+        Recorder::new(StreamReader::new(self.signal().clone()), writer)
+    }
+}
+
+impl<'a, T: Data + Serialize + 'a> CollectionSignal<'a, T> {
+    // The first recorded change is the current content of the collection
+    pub fn record<W: Write>(&self, writer: W) -> Recorder<'a, SetChange<T>, W> {
+        Recorder::new(StreamReader::new(self.signal().clone()), writer)
+    }
+}
+
+fn into_io_error(error: bincode::ErrorKind) -> io::Error {
+    match error {
+        bincode::ErrorKind::Io(error) => error,
+        error => io::Error::new(io::ErrorKind::InvalidData, error),
+    }
+}
+
+// Pushes every recorded item into the portal and returns how many there were
+pub fn replay_into<T: DeserializeOwned, P: FnMut(T), R: BufRead>(
+    mut portal: P,
+    mut reader: R,
+) -> io::Result<usize> {
+    let mut replayed = 0;
+    while !reader.fill_buf()?.is_empty() {
+        portal(bincode::deserialize_from(&mut reader).map_err(|e| into_io_error(*e))?);
+        replayed += 1;
+    }
+    Ok(replayed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::{CollectionPortal, VecView, View};
+
+    #[test]
+    fn test_record_replay_stream() {
+        let world = World::new();
+        let (send, stream) = portal(&world);
+        let mut recorder = stream.record(Vec::new());
+        send((1, "a".to_owned()));
+        send((2, "b".to_owned()));
+        assert_eq!(recorder.update().unwrap(), 2);
+        assert_eq!(recorder.update().unwrap(), 0);
+        let log = recorder.into_inner();
+
+        let (send2, stream2) = portal::<(i32, String)>(&world);
+        let mut collector = stream2.collect();
+        assert_eq!(replay_into(&send2, log.as_slice()).unwrap(), 2);
+        collector.update();
+        assert_eq!(
+            collector.drain().collect::<Vec<_>>(),
+            vec![(1, "a".to_owned()), (2, "b".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_record_replay_collection() {
+        let world = World::new();
+        let mut setp: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        setp.extend(vec![1, 2]);
+        let mut recorder = setp.signal().record(Vec::new());
+        setp.add(3);
+        setp.replace(1, 4);
+        setp.remove(2);
+        recorder.update().unwrap();
+        let log = recorder.into_inner();
+
+        let mut copy: CollectionPortal<i32> = CollectionPortal::new::<VecView<i32>>(&world);
+        let mut view = copy.signal().view_vec();
+        replay_into(|c| copy.apply(c), log.as_slice()).unwrap();
+        let mut items: Vec<i32> = view.iter().copied().collect();
+        items.sort_unstable();
+        assert_eq!(items, vec![3, 4]);
+    }
+
+    #[test]
+    fn test_replay_truncated_log() {
+        let world = World::new();
+        let (send, stream) = portal(&world);
+        let mut recorder = stream.record(Vec::new());
+        send(1u32);
+        send(2u32);
+        recorder.update().unwrap();
+        let mut log = recorder.into_inner();
+        log.pop();
+        let (send2, _stream2) = portal::<u32>(&world);
+        let err = replay_into(&send2, log.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
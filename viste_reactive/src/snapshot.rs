@@ -0,0 +1,124 @@
+use crate::values::folder::Folder;
+use crate::values::mutable::Mutable;
+use crate::*;
+use std::any::Any;
+
+pub trait Stateful {
+    fn save(&self) -> Rc<dyn Any>;
+    fn restore(&mut self, state: &dyn Any);
+}
+
+// The saved states of all keyed nodes of a world, values are shared instead of cloned
+#[derive(Clone, Default)]
+pub struct Snapshot {
+    states: HashMap<String, Rc<dyn Any>>,
+}
+
+impl Snapshot {
+    pub fn new(states: HashMap<String, Rc<dyn Any>>) -> Self {
+        Self { states }
+    }
+
+    pub fn get<T: 'static>(&self, key: &str) -> Option<&T> {
+        self.states.get(key)?.downcast_ref()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Rc<dyn Any>)> {
+        self.states.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+}
+
+pub fn mutable_keyed<'a, T: Data + 'static>(
+    world: &World,
+    key: &str,
+    initial: T,
+) -> (impl Fn(T), ValueSignal<'a, T>) {
+    let m = Rc::new(RefCell::new(Mutable::new(world.clone(), initial)));
+    let stateful: Rc<RefCell<dyn Stateful>> = m.clone();
+    world.register_stateful(key, Rc::downgrade(&stateful));
+    let s = m.clone();
+    let mutator = move |v| s.borrow_mut().set(v);
+    (mutator, ValueSignal::new(Signal(m)))
+}
+
+impl<T: Data + 'static> StreamSignal<'static, T> {
+    pub fn fold_keyed<V: Data + 'static, F: Fn(V, T) -> V + 'static>(
+        &self,
+        key: &str,
+        folder: F,
+        initial: V,
+    ) -> ValueSignal<'static, V> {
+        let world = self.signal().world();
+        let f = Rc::new(RefCell::new(Folder::new(
+            world.clone(),
+            self.clone(),
+            initial,
+            folder,
+        )));
+        let stateful: Rc<RefCell<dyn Stateful>> = f.clone();
+        world.register_stateful(key, Rc::downgrade(&stateful));
+        ValueSignal::new(Signal(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_restore() {
+        let world = World::new();
+        let (set, name) = mutable_keyed(&world, "name", "a".to_owned());
+        let (send, stream) = portal(&world);
+        let sum = stream.fold_keyed("sum", |acc, i: i32| acc + i, 0);
+        let label = map2(&name, &sum, |n, s| format!("{}{}", n, s));
+        send(1);
+        send(2);
+        assert_eq!(read_once(&label), "a3");
+
+        let snapshot = world.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot.get::<i32>("sum"), Some(&3));
+        assert_eq!(snapshot.get::<String>("name"), Some(&"a".to_owned()));
+
+        set("b".to_owned());
+        send(10);
+        assert_eq!(read_once(&label), "b13");
+        world.restore(&snapshot);
+        assert_eq!(read_once(&label), "a3");
+        send(4);
+        assert_eq!(read_once(&label), "a7");
+    }
+
+    #[test]
+    fn test_snapshot_dropped_nodes() {
+        let world = World::new();
+        let (_set, kept) = mutable_keyed(&world, "kept", 1);
+        let (set, dropped) = mutable_keyed(&world, "dropped", 1);
+        let snapshot = world.snapshot();
+        drop(set);
+        drop(dropped);
+        assert_eq!(world.snapshot().len(), 1);
+        // The key of a dropped node may be reused
+        let (_set2, replacement) = mutable_keyed(&world, "dropped", 5);
+        world.restore(&snapshot);
+        assert_eq!(read_once(&kept), 1);
+        assert_eq!(read_once(&replacement), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "already registered")]
+    fn test_duplicate_key() {
+        let world = World::new();
+        let _first = mutable_keyed(&world, "key", 1);
+        let _second = mutable_keyed(&world, "key", 2);
+    }
+}
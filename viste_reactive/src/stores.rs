@@ -1,9 +1,15 @@
 use crate::*;
+use std::collections::HashMap;
 
 //Should this also contain the read method and the type?
 pub trait Store {
     fn create_reader(&mut self) -> ReaderToken;
     fn destroy_reader(&mut self, reader: ReaderToken);
+
+    // Number of values the reader missed because the store overflowed
+    fn take_lag(&mut self, _reader: ReaderToken) -> usize {
+        0
+    }
 }
 
 pub struct SingleValueStore<T: Data> {
@@ -30,6 +36,14 @@ impl<T: Data> SingleValueStore<T> {
             .for_each(|(_, rs)| *rs = false);
     }
 
+    // Notifies all readers without comparing against the previous value
+    pub fn replace_value(&mut self, value: T) {
+        self.value = value;
+        self.reader_states
+            .iter_mut()
+            .for_each(|(_, rs)| *rs = false);
+    }
+
     pub fn read(&mut self, reader: ReaderToken) -> SingleComputationResult<T> {
         let state = self
             .reader_states
@@ -59,26 +73,175 @@ impl<T: Data> Store for SingleValueStore<T> {
     }
 }
 
+pub enum OverflowPolicy<T> {
+    DropOldest,
+    DropNewest,
+    // Portals hand the rejected value back to the sender, derived nodes drop it and report lag
+    Error,
+    Coalesce(Box<dyn Fn(T, T) -> T>),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BufferOverflow<T>(pub T);
+
+struct BufferReaderState {
+    position: usize,
+    lagged: usize,
+}
+
+// Values are kept once in a shared log, together with the number of readers that have yet to read them.
+// Readers only keep their absolute position in the log, the front is dropped once everyone has advanced past it.
 pub struct BufferedStore<T: Data> {
-    //TODO: Optimize with single queue and position as state?
-    reader_states: Slab<VecDeque<T>>,
+    buffer: VecDeque<(T, usize)>,
+    offset: usize,
+    limit: Option<(usize, OverflowPolicy<T>)>,
+    complete: bool,
+    reader_states: Slab<BufferReaderState>,
 }
 
 impl<T: Data> BufferedStore<T> {
     pub fn new() -> Self {
         Self {
+            buffer: VecDeque::new(),
+            offset: 0,
+            limit: None,
+            complete: false,
             reader_states: Slab::new(),
         }
     }
 
-    pub fn read(&mut self, reader: ReaderToken) -> Option<T> {
-        self.reader_states[reader.0].pop_front()
+    pub fn bounded(capacity: usize, policy: OverflowPolicy<T>) -> Self {
+        assert!(capacity > 0, "Buffer capacity must be at least 1");
+        Self {
+            limit: Some((capacity, policy)),
+            ..Self::new()
+        }
+    }
+
+    fn end(&self) -> usize {
+        self.offset + self.buffer.len()
+    }
+
+    fn truncate(&mut self) {
+        while let Some((_, 0)) = self.buffer.front() {
+            self.buffer.pop_front();
+            self.offset += 1;
+        }
+    }
+
+    pub fn read(&mut self, reader: ReaderToken) -> StreamEvent<T> {
+        let end = self.end();
+        let state = self
+            .reader_states
+            .get_mut(reader.0)
+            .expect("Reader not found");
+        if state.position == end {
+            return if self.complete {
+                StreamEvent::Complete
+            } else {
+                StreamEvent::Pending
+            };
+        }
+        let (value, remaining) = &mut self.buffer[state.position - self.offset];
+        state.position += 1;
+        *remaining -= 1;
+        let value = value.cheap_clone();
+        self.truncate();
+        StreamEvent::Item(value)
     }
 
+    // Derived nodes have nobody to hand a rejected value back to, so it is reported as lag to every reader
     pub fn push(&mut self, value: T) {
+        if let Err(BufferOverflow(_)) = self.try_push(value) {
+            self.lag_all();
+        }
+    }
+
+    // Once complete, readers receive the remaining values followed by StreamEvent::Complete
+    pub fn complete(&mut self) {
+        self.complete = true;
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    pub fn try_push(&mut self, value: T) -> Result<(), BufferOverflow<T>> {
+        let readers = self.reader_states.len();
+        if readers == 0 || self.complete {
+            return Ok(());
+        }
+        match &self.limit {
+            Some((capacity, policy)) if self.buffer.len() >= *capacity => match policy {
+                OverflowPolicy::DropOldest => {
+                    self.drop_oldest();
+                    self.buffer.push_back((value, readers));
+                }
+                OverflowPolicy::DropNewest => self.lag_all(),
+                OverflowPolicy::Error => return Err(BufferOverflow(value)),
+                OverflowPolicy::Coalesce(_) => {
+                    self.buffer.push_back((value, readers));
+                    self.coalesce();
+                }
+            },
+            _ => self.buffer.push_back((value, readers)),
+        }
+        Ok(())
+    }
+
+    fn lag_all(&mut self) {
+        self.reader_states
+            .iter_mut()
+            .for_each(|(_, rs)| rs.lagged += 1);
+    }
+
+    fn drop_oldest(&mut self) {
+        let offset = self.offset;
+        self.buffer.pop_front();
+        self.offset += 1;
         self.reader_states
             .iter_mut()
-            .for_each(|(_, rs)| rs.push_back(value.cheap_clone()))
+            .filter(|(_, rs)| rs.position == offset)
+            .for_each(|(_, rs)| {
+                rs.position += 1;
+                rs.lagged += 1;
+            });
+    }
+
+    // Merges the oldest pair of neighbouring values that no reader sits between, so every reader either sees
+    // both values or the merged one. If readers sit between every pair, the newest two values are merged anyway
+    // and the readers between them skip the merged value, which counts as lag.
+    fn coalesce(&mut self) {
+        let offset = self.offset;
+        let occupied: Vec<usize> = self
+            .reader_states
+            .iter()
+            .map(|(_, rs)| rs.position - offset)
+            .collect();
+        let newest = self.buffer.len() - 1;
+        let idx = (1..newest)
+            .find(|idx| !occupied.contains(idx))
+            .unwrap_or(newest);
+        if let Some((_, OverflowPolicy::Coalesce(f))) = &self.limit {
+            let (newer, _) = self.buffer.remove(idx).expect("Index must exist");
+            let (older, remaining) = self.buffer.remove(idx - 1).expect("Index must exist");
+            self.buffer.insert(idx - 1, (f(older, newer), remaining));
+            self.reader_states.iter_mut().for_each(|(_, rs)| {
+                if rs.position == offset + idx {
+                    rs.lagged += 1;
+                } else if rs.position > offset + idx {
+                    rs.position -= 1;
+                }
+            });
+        }
+    }
+
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn readers(&self) -> usize {
+        self.reader_states.len()
     }
 }
 
@@ -90,10 +253,91 @@ impl<T: Data> Default for BufferedStore<T> {
 
 impl<T: Data> Store for BufferedStore<T> {
     fn create_reader(&mut self) -> ReaderToken {
-        ReaderToken(self.reader_states.insert(VecDeque::new()))
+        let position = self.end();
+        ReaderToken(self.reader_states.insert(BufferReaderState {
+            position,
+            lagged: 0,
+        }))
     }
 
     fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.reader_states.remove(reader.0);
+        let position = self.reader_states.remove(reader.0).position;
+        let offset = self.offset;
+        self.buffer
+            .iter_mut()
+            .skip(position - offset)
+            .for_each(|(_, remaining)| *remaining -= 1);
+        self.truncate();
+    }
+
+    fn take_lag(&mut self, reader: ReaderToken) -> usize {
+        let state = self
+            .reader_states
+            .get_mut(reader.0)
+            .expect("Reader not found");
+        std::mem::take(&mut state.lagged)
+    }
+}
+
+// Keeps the last `capacity` values around and hands them to every newly created reader
+// before it continues with the live values.
+pub struct ReplayStore<T: Data> {
+    capacity: usize,
+    history: VecDeque<T>,
+    seeds: HashMap<ReaderToken, VecDeque<T>>,
+    store: BufferedStore<T>,
+}
+
+impl<T: Data> ReplayStore<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            history: VecDeque::with_capacity(capacity),
+            seeds: HashMap::new(),
+            store: BufferedStore::new(),
+        }
+    }
+
+    pub fn read(&mut self, reader: ReaderToken) -> StreamEvent<T> {
+        match self.seeds.get_mut(&reader).and_then(VecDeque::pop_front) {
+            Some(seeded) => StreamEvent::Item(seeded),
+            None => {
+                self.seeds.remove(&reader);
+                self.store.read(reader)
+            }
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.capacity > 0 {
+            if self.history.len() == self.capacity {
+                self.history.pop_front();
+            }
+            self.history.push_back(value.cheap_clone());
+        }
+        self.store.push(value)
+    }
+
+    pub fn complete(&mut self) {
+        self.store.complete()
+    }
+
+    pub fn history(&self) -> impl Iterator<Item = &T> {
+        self.history.iter()
+    }
+}
+
+impl<T: Data> Store for ReplayStore<T> {
+    fn create_reader(&mut self) -> ReaderToken {
+        let reader = self.store.create_reader();
+        if !self.history.is_empty() {
+            self.seeds.insert(reader, self.history.clone());
+        }
+        reader
+    }
+
+    fn destroy_reader(&mut self, reader: ReaderToken) {
+        self.seeds.remove(&reader);
+        self.store.destroy_reader(reader)
     }
 }
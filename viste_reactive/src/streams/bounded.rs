@@ -0,0 +1,49 @@
+use crate::stores::{BufferedStore, OverflowPolicy};
+use crate::*;
+
+pub struct Bounded<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Bounded<'a, T> {
+    pub fn new(
+        world: World,
+        source: StreamSignal<'a, T>,
+        capacity: usize,
+        policy: OverflowPolicy<T>,
+    ) -> Self {
+        let node = NodeState::new(world);
+        let source = ParentSignal::new(source.0, node.node());
+        Self {
+            source,
+            store: BufferedStore::bounded(capacity, policy),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Bounded<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let store = &mut self.store;
+            if self.source.drain_items(|next| store.push(next)) {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
@@ -0,0 +1,57 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct BufferUntil<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    trigger: ParentStreamSignal<'a, ()>,
+    current: Vec<T>,
+    store: BufferedStore<Vec<T>>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> BufferUntil<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, trigger: StreamSignal<'a, ()>) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            trigger: ParentSignal::new(trigger.0, node.node()),
+            current: Vec::new(),
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for BufferUntil<'a, T> {
+    type Output = StreamEvent<Vec<T>>;
+    type Store = BufferedStore<Vec<T>>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            // Items and triggers arriving between two computations can not be ordered,
+            // so all pending items belong to the first pending trigger and every further trigger flushes an empty buffer
+            let current = &mut self.current;
+            let source_complete = self.source.drain_items(|next| current.push(next));
+            let store = &mut self.store;
+            let trigger_complete = self
+                .trigger
+                .drain_items(|_| store.push(std::mem::take(current)));
+            if source_complete || trigger_complete {
+                if !current.is_empty() {
+                    store.push(std::mem::take(current))
+                }
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::stores::{BufferedStore, Store};
+use crate::stores::{BufferedStore, OverflowPolicy};
 use crate::*;
 
 pub struct Cached<'a, T: Data + 'a> {
@@ -10,63 +10,62 @@ pub struct Cached<'a, T: Data + 'a> {
 
 impl<'a, T: Data + 'a> Cached<'a, T> {
     pub fn new(world: World, source: StreamSignal<'a, T>) -> Self {
+        Self::with_store(world, source, BufferedStore::new())
+    }
+
+    pub fn bounded(
+        world: World,
+        source: StreamSignal<'a, T>,
+        capacity: usize,
+        policy: OverflowPolicy<T>,
+    ) -> Self {
+        Self::with_store(world, source, BufferedStore::bounded(capacity, policy))
+    }
+
+    fn with_store(world: World, source: StreamSignal<'a, T>, store: BufferedStore<T>) -> Self {
         let node = NodeState::new(world);
         let source = ParentSignal::new(source.0, node.node());
         Self {
             source,
             last: None,
-            store: BufferedStore::new(),
+            store,
             node,
         }
     }
 }
 
-impl<'a, T: Data + 'a> ComputationCore for Cached<'a, T> {
-    type ComputationResult = Option<T>;
+impl<'a, T: Data + 'a> Operator for Cached<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
 
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
         if self.is_dirty() {
             self.node.clean();
-            while let Some(next) = self.source.compute() {
-                if let Some(last) = self.last.take() {
-                    if next.changed(&last) {
-                        self.store.push(next.cheap_clone())
+            let store = &mut self.store;
+            let last = &mut self.last;
+            let complete = self.source.drain_items(|next| {
+                if let Some(previous) = last.take() {
+                    if next.changed(&previous) {
+                        store.push(next.cheap_clone())
                     }
-                    self.last = Some(next)
+                    *last = Some(next)
                 } else {
-                    self.last = Some(next.cheap_clone());
-                    self.store.push(next)
+                    *last = Some(next.cheap_clone());
+                    store.push(next)
                 }
+            });
+            if complete {
+                store.complete()
             }
         }
         self.store.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.store.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.store.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
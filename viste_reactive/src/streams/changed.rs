@@ -1,4 +1,4 @@
-use crate::stores::{BufferedStore, Store};
+use crate::stores::BufferedStore;
 use crate::*;
 
 pub struct Changed<'a, T: Data + 'a> {
@@ -18,10 +18,19 @@ impl<'a, T: Data + 'a> Changed<'a, T> {
     }
 }
 
-impl<'a, T: Data + 'a> ComputationCore for Changed<'a, T> {
-    type ComputationResult = Option<T>;
+impl<'a, T: Data + 'a> Operator for Changed<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
 
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
         if self.is_dirty() {
             self.node.clean();
             if let SingleComputationResult::Changed(new) = self.source.compute() {
@@ -30,32 +39,4 @@ impl<'a, T: Data + 'a> ComputationCore for Changed<'a, T> {
         }
         self.store.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.store.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.store.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
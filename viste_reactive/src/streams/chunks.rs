@@ -0,0 +1,60 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Chunks<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    size: usize,
+    current: Vec<T>,
+    store: BufferedStore<Vec<T>>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Chunks<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, size: usize) -> Self {
+        assert!(size > 0, "Chunk size must be at least 1");
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            size,
+            current: Vec::with_capacity(size),
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Chunks<'a, T> {
+    type Output = StreamEvent<Vec<T>>;
+    type Store = BufferedStore<Vec<T>>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let store = &mut self.store;
+            let current = &mut self.current;
+            let size = self.size;
+            let complete = self.source.drain_items(|next| {
+                current.push(next);
+                if current.len() == size {
+                    store.push(replace(current, Vec::with_capacity(size)))
+                }
+            });
+            if complete {
+                //The last chunk may be shorter
+                if !current.is_empty() {
+                    store.push(std::mem::take(current))
+                }
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::stores::{BufferedStore, Store};
+use crate::stores::BufferedStore;
 use crate::*;
 
 pub struct CombineMapper<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, M: Fn(I1, I2) -> O + 'a> {
@@ -35,22 +35,32 @@ impl<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, M: Fn(I1, I2) -> O + 'a>
     }
 }
 
-impl<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, M: Fn(I1, I2) -> O + 'a> ComputationCore
+impl<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, M: Fn(I1, I2) -> O + 'a> Operator
     for CombineMapper<'a, I1, I2, O, M>
 {
-    type ComputationResult = Option<O>;
+    type Output = StreamEvent<O>;
+    type Store = BufferedStore<O>;
 
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    #[allow(clippy::single_match)]
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
         if self.is_dirty() {
             self.node.clean();
             match (self.source1.compute(), self.source2.compute()) {
-                (Some(v1), Some(v2)) => {
+                (StreamEvent::Item(v1), StreamEvent::Item(v2)) => {
                     self.store
                         .push((self.mapper)(v1.cheap_clone(), v2.cheap_clone()));
                     self.cached_value1 = Some(v1);
                     self.cached_value2 = Some(v2);
                 }
-                (Some(v1), None) => {
+                (StreamEvent::Item(v1), _) => {
                     match &self.cached_value2 {
                         Some(v2) => self
                             .store
@@ -59,7 +69,7 @@ impl<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, M: Fn(I1, I2) -> O + 'a> Co
                     }
                     self.cached_value1 = Some(v1)
                 }
-                (None, Some(v2)) => {
+                (_, StreamEvent::Item(v2)) => {
                     match &self.cached_value1 {
                         Some(v1) => self
                             .store
@@ -68,37 +78,10 @@ impl<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, M: Fn(I1, I2) -> O + 'a> Co
                     }
                     self.cached_value2 = Some(v2)
                 }
-                (None, None) => (),
+                (StreamEvent::Complete, StreamEvent::Complete) => self.store.complete(),
+                _ => (),
             }
         }
         self.store.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.store.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.store.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
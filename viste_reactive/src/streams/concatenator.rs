@@ -0,0 +1,68 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Concatenator<'a, T: Data + 'a> {
+    outer: ParentStreamSignal<'a, StreamSignal<'a, T>>,
+    queued: VecDeque<StreamSignal<'a, T>>,
+    current: Option<ParentStreamSignal<'a, T>>,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Concatenator<'a, T> {
+    pub fn new(world: World, outer: StreamSignal<'a, StreamSignal<'a, T>>) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            outer: ParentSignal::new(outer.0, node.node()),
+            queued: VecDeque::new(),
+            current: None,
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Concatenator<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let queued = &mut self.queued;
+            let outer_complete = self.outer.drain_items(|inner| queued.push_back(inner));
+            // Streams are only subscribed to once all previous ones completed
+            loop {
+                if self.current.is_none() {
+                    match self.queued.pop_front() {
+                        Some(next) => {
+                            self.current = Some(ParentSignal::new(next.0, self.node.node()))
+                        }
+                        None => break,
+                    }
+                }
+                let store = &mut self.store;
+                let current = self.current.as_mut().expect("Current stream must exist");
+                if !current.drain_items(|next| store.push(next)) {
+                    break;
+                }
+                match self.queued.pop_front() {
+                    Some(next) => current.set_parent(next.0),
+                    None => self.current = None,
+                }
+            }
+            if outer_complete && self.current.is_none() {
+                self.store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
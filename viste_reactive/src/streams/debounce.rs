@@ -0,0 +1,69 @@
+use crate::stores::BufferedStore;
+use crate::*;
+use std::time::Duration;
+
+pub struct Debounce<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    period: Duration,
+    pending: Option<(Duration, T)>,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Debounce<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, period: Duration) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            period,
+            pending: None,
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Debounce<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let now = self.node.world().now();
+            let deadline = now + self.period;
+            // A value whose quiet period passed while nobody read the node must not be replaced
+            if let Some((at, _)) = &self.pending {
+                if *at <= now {
+                    let (_, value) = self.pending.take().expect("Pending must exist");
+                    self.store.push(value);
+                }
+            }
+            let pending = &mut self.pending;
+            let mut received = false;
+            let complete = self.source.drain_items(|next| {
+                received = true;
+                *pending = Some((deadline, next));
+            });
+            if received {
+                self.node.schedule(deadline);
+            }
+            match self.pending.take() {
+                Some((at, value)) if at <= now || complete => self.store.push(value),
+                waiting => self.pending = waiting,
+            }
+            if complete {
+                self.store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
@@ -0,0 +1,62 @@
+use crate::stores::BufferedStore;
+use crate::*;
+use std::time::Duration;
+
+pub struct Delay<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    period: Duration,
+    queue: VecDeque<(Duration, T)>,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Delay<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, period: Duration) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            period,
+            queue: VecDeque::new(),
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Delay<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let now = self.node.world().now();
+            let due = now + self.period;
+            let queue = &mut self.queue;
+            let queued = queue.len();
+            let complete = self.source.drain_items(|next| queue.push_back((due, next)));
+            if queue.len() > queued {
+                self.node.schedule(due);
+            }
+            while let Some((at, _)) = queue.front() {
+                if *at > now {
+                    break;
+                }
+                let (_, value) = queue.pop_front().expect("Front must exist");
+                self.store.push(value);
+            }
+            if complete && queue.is_empty() {
+                self.store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Differ<'a, C: Data + 'a, T: Data + 'a, D: Fn(&C, &C) -> Vec<T> + 'a> {
+    source: ParentValueSignal<'a, C>,
+    previous: C,
+    store: BufferedStore<T>,
+    differ: D,
+    node: NodeState,
+}
+
+impl<'a, C: Data + 'a, T: Data + 'a, D: Fn(&C, &C) -> Vec<T> + 'a> Differ<'a, C, T, D> {
+    pub fn new(world: World, source: ValueSignal<'a, C>, initial: C, differ: D) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentValueSignal::new(source.0, node.node()),
+            previous: initial,
+            store: BufferedStore::new(),
+            differ,
+            node,
+        }
+    }
+}
+
+impl<'a, C: Data + 'a, T: Data + 'a, D: Fn(&C, &C) -> Vec<T> + 'a> Operator
+    for Differ<'a, C, T, D>
+{
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            if let SingleComputationResult::Changed(new) = self.source.compute() {
+                for change in (self.differ)(&self.previous, &new) {
+                    self.store.push(change)
+                }
+                self.previous = new;
+            }
+        }
+        self.store.read(reader)
+    }
+}
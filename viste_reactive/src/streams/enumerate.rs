@@ -0,0 +1,50 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Enumerate<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    index: usize,
+    store: BufferedStore<(usize, T)>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Enumerate<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            index: 0,
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Enumerate<'a, T> {
+    type Output = StreamEvent<(usize, T)>;
+    type Store = BufferedStore<(usize, T)>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let store = &mut self.store;
+            let index = &mut self.index;
+            let complete = self.source.drain_items(|next| {
+                store.push((*index, next));
+                *index += 1;
+            });
+            if complete {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::stores::{BufferedStore, Store};
+use crate::stores::BufferedStore;
 use crate::*;
 
 pub struct Filter<'a, T: Data, F: Fn(&T) -> bool + 'a> {
@@ -9,7 +9,7 @@ pub struct Filter<'a, T: Data, F: Fn(&T) -> bool + 'a> {
 }
 
 impl<'a, T: Data, F: Fn(&T) -> bool + 'a> Filter<'a, T, F> {
-    pub fn new(world: World, source: Signal<'a, Option<T>>, filter: F) -> Self {
+    pub fn new(world: World, source: Signal<'a, StreamEvent<T>>, filter: F) -> Self {
         let node = NodeState::new(world);
         let source = ParentSignal::new(source, node.node());
         Self {
@@ -21,46 +21,32 @@ impl<'a, T: Data, F: Fn(&T) -> bool + 'a> Filter<'a, T, F> {
     }
 }
 
-impl<'a, T: Data, F: Fn(&T) -> bool + 'a> ComputationCore for Filter<'a, T, F> {
-    type ComputationResult = Option<T>;
+impl<'a, T: Data, F: Fn(&T) -> bool + 'a> Operator for Filter<'a, T, F> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
 
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
         if self.is_dirty() {
             self.node.clean();
-            while let Some(next) = self.source.compute() {
-                if (self.filter)(&next) {
-                    self.store.push(next)
+            let store = &mut self.store;
+            let filter = &self.filter;
+            let complete = self.source.drain_items(|next| {
+                if filter(&next) {
+                    store.push(next)
                 }
+            });
+            if complete {
+                store.complete()
             }
         }
         self.store.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.store.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.store.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
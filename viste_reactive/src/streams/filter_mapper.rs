@@ -1,4 +1,4 @@
-use crate::stores::{BufferedStore, Store};
+use crate::stores::BufferedStore;
 use crate::*;
 
 pub struct FilterMapper<'a, T: Data + 'a, O: Data + 'a, F: Fn(T) -> Option<O> + 'a> {
@@ -9,7 +9,7 @@ pub struct FilterMapper<'a, T: Data + 'a, O: Data + 'a, F: Fn(T) -> Option<O> +
 }
 
 impl<'a, T: Data + 'a, O: Data + 'a, F: Fn(T) -> Option<O> + 'a> FilterMapper<'a, T, O, F> {
-    pub fn new(world: World, source: Signal<'a, Option<T>>, fmap: F) -> Self {
+    pub fn new(world: World, source: Signal<'a, StreamEvent<T>>, fmap: F) -> Self {
         let node = NodeState::new(world);
         Self {
             source: ParentSignal::new(source, node.node()),
@@ -20,48 +20,34 @@ impl<'a, T: Data + 'a, O: Data + 'a, F: Fn(T) -> Option<O> + 'a> FilterMapper<'a
     }
 }
 
-impl<'a, T: Data + 'a, O: Data + 'a, F: Fn(T) -> Option<O> + 'a> ComputationCore
+impl<'a, T: Data + 'a, O: Data + 'a, F: Fn(T) -> Option<O> + 'a> Operator
     for FilterMapper<'a, T, O, F>
 {
-    type ComputationResult = Option<O>;
+    type Output = StreamEvent<O>;
+    type Store = BufferedStore<O>;
 
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
         if self.is_dirty() {
             self.node.clean();
-            while let Some(t) = self.source.compute() {
-                if let Some(v) = (self.fmap)(t) {
-                    self.store.push(v)
+            let store = &mut self.store;
+            let fmap = &self.fmap;
+            let complete = self.source.drain_items(|t| {
+                if let Some(v) = fmap(t) {
+                    store.push(v)
                 }
+            });
+            if complete {
+                store.complete()
             }
         }
         self.store.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.store.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.store.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
@@ -1,4 +1,4 @@
-use crate::stores::{BufferedStore, Store};
+use crate::stores::BufferedStore;
 use crate::*;
 
 pub struct FromIter<'a, T: Data + 'a> {
@@ -17,47 +17,28 @@ impl<'a, T: Data + 'a> FromIter<'a, T> {
     }
 }
 
-impl<'a, T: Data + 'a> ComputationCore for FromIter<'a, T> {
-    type ComputationResult = Option<T>;
+impl<'a, T: Data + 'a> Operator for FromIter<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
 
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
-        match self.store.read(reader) {
-            Some(x) => Some(x),
-            None => match self.iterator.next() {
-                Some(next) => {
-                    self.store.push(next.cheap_clone());
-                    Some(next)
-                }
-                None => None,
-            },
-        }
-    }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.store.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.store.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
+    fn node_state(&self) -> &NodeState {
+        &self.node
     }
 
-    fn world(&self) -> World {
-        self.node.world().clone()
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
     }
 
-    fn node(&self) -> NodeIndex {
-        self.node.node()
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        match self.store.read(reader) {
+            StreamEvent::Pending => {
+                match self.iterator.next() {
+                    Some(next) => self.store.push(next),
+                    None => self.store.complete(),
+                }
+                self.store.read(reader)
+            }
+            event => event,
+        }
     }
 }
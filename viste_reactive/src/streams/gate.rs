@@ -0,0 +1,52 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Gate<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    open: ParentCachedValueSignal<'a, bool>,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Gate<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, open: ValueSignal<'a, bool>) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            open: ParentSignal::new(open.0, node.node()),
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Gate<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let (_, open) = self.open.compute();
+            let store = &mut self.store;
+            // Items arriving while the gate is closed are dropped
+            let complete = self.source.drain_items(|next| {
+                if open {
+                    store.push(next)
+                }
+            });
+            if complete {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::stores::SingleValueStore;
+use crate::*;
+
+// Like Last, but every new item counts as a change, so values are never compared
+pub struct Hold<'a, T: Data + 'a> {
+    source: Option<ParentStreamSignal<'a, T>>,
+    value: SingleValueStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Hold<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, initial: T) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: Some(ParentSignal::new(source.0, node.node())),
+            value: SingleValueStore::new(initial),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Hold<'a, T> {
+    type Output = SingleComputationResult<T>;
+    type Store = SingleValueStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.value
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            if let Some(source) = &mut self.source {
+                let mut last = None;
+                let complete = source.drain_items(|nv| last = Some(nv));
+                if let Some(l) = last {
+                    self.value.replace_value(l)
+                }
+                if complete {
+                    self.source = None;
+                }
+            }
+        }
+        self.value.read(reader)
+    }
+}
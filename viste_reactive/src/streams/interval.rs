@@ -0,0 +1,60 @@
+use crate::stores::BufferedStore;
+use crate::*;
+use std::time::Duration;
+
+pub struct Interval {
+    period: Duration,
+    next: Duration,
+    count: u64,
+    store: BufferedStore<u64>,
+    node: NodeState,
+}
+
+impl Interval {
+    pub fn new(world: World, period: Duration) -> Self {
+        assert!(
+            period > Duration::from_secs(0),
+            "Interval period must not be zero"
+        );
+        let node = NodeState::new(world);
+        let next = node.world().now() + period;
+        node.schedule(next);
+        Self {
+            period,
+            next,
+            count: 0,
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl Operator for Interval {
+    type Output = StreamEvent<u64>;
+    type Store = BufferedStore<u64>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.node.is_dirty() {
+            self.node.clean();
+            let now = self.node.world().now();
+            let previous = self.next;
+            while self.next <= now {
+                self.store.push(self.count);
+                self.count += 1;
+                self.next += self.period;
+            }
+            if self.next != previous {
+                self.node.schedule(self.next);
+            }
+        }
+        self.store.read(reader)
+    }
+}
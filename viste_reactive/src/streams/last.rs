@@ -1,8 +1,9 @@
-use crate::stores::{SingleValueStore, Store};
+use crate::stores::SingleValueStore;
 use crate::*;
 
 pub struct Last<'a, T: Data + 'a> {
-    source: ParentStreamSignal<'a, T>,
+    //Released once the source stream completes
+    source: Option<ParentStreamSignal<'a, T>>,
     value: SingleValueStore<T>,
     node: NodeState,
 }
@@ -11,56 +12,39 @@ impl<'a, T: Data + 'a> Last<'a, T> {
     pub fn new(world: World, source: StreamSignal<'a, T>, initial: T) -> Self {
         let node = NodeState::new(world);
         Self {
-            source: ParentSignal::new(source.0, node.node()),
+            source: Some(ParentSignal::new(source.0, node.node())),
             value: SingleValueStore::new(initial),
             node,
         }
     }
 }
 
-impl<'a, T: Data + 'a> ComputationCore for Last<'a, T> {
-    type ComputationResult = SingleComputationResult<T>;
+impl<'a, T: Data + 'a> Operator for Last<'a, T> {
+    type Output = SingleComputationResult<T>;
+    type Store = SingleValueStore<T>;
 
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.value
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
         if self.is_dirty() {
             self.node.clean();
-            let mut last = None;
-            while let Some(nv) = self.source.compute() {
-                last = Some(nv);
-            }
-            match last {
-                None => (),
-                Some(l) => self.value.set_value(l),
+            if let Some(source) = &mut self.source {
+                let mut last = None;
+                let complete = source.drain_items(|nv| last = Some(nv));
+                if let Some(l) = last {
+                    self.value.set_value(l)
+                }
+                if complete {
+                    self.source = None;
+                }
             }
         }
         self.value.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.value.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.value.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
@@ -1,4 +1,4 @@
-use crate::stores::{BufferedStore, Store};
+use crate::stores::BufferedStore;
 use crate::*;
 use std::collections::HashMap;
 
@@ -28,17 +28,28 @@ impl<'a, T: Data + 'a> Many<'a, T> {
     }
 }
 
-impl<'a, T: Data + 'a> ComputationCore for Many<'a, T> {
-    type ComputationResult = Option<T>;
+impl<'a, T: Data + 'a> Operator for Many<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
 
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.values
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
         let dirty_state = self.node.reset_dirty_state();
+        let values = &mut self.values;
+        let mut completed = Vec::new();
         match dirty_state {
             DirtyFlag::Basic(false) => (),
             DirtyFlag::Basic(true) => {
-                for (_, source) in self.sources.iter_mut() {
-                    while let Some(val) = source.compute() {
-                        self.values.push(val)
+                for (node, source) in self.sources.iter_mut() {
+                    if source.drain_items(|val| values.push(val)) {
+                        completed.push(*node);
                     }
                 }
             }
@@ -48,41 +59,19 @@ impl<'a, T: Data + 'a> ComputationCore for Many<'a, T> {
                         .sources
                         .get_mut(&changed_node)
                         .expect("Dirtied parent node not found");
-                    while let Some(val) = source.compute() {
-                        self.values.push(val)
+                    if source.drain_items(|val| values.push(val)) {
+                        completed.push(changed_node);
                     }
                 }
             }
         }
+        for node in completed {
+            self.sources.remove(&node);
+        }
+        if self.sources.is_empty() {
+            values.complete();
+        }
 
         self.values.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.values.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.values.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
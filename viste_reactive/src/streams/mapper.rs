@@ -1,5 +1,4 @@
-use crate::graph::NodeIndex;
-use crate::stores::{BufferedStore, Store};
+use crate::stores::{BufferedStore, OverflowPolicy};
 use crate::*;
 
 pub struct Mapper<'a, T: Data + 'a, R: Data + 'a, M: Fn(T) -> R + 'a> {
@@ -10,55 +9,62 @@ pub struct Mapper<'a, T: Data + 'a, R: Data + 'a, M: Fn(T) -> R + 'a> {
 }
 
 impl<'a, T: Data + 'a, R: Data + 'a, M: Fn(T) -> R + 'a> Mapper<'a, T, R, M> {
-    pub fn new(world: World, source: Signal<'a, Option<T>>, mapper: M) -> Self {
+    pub fn new(world: World, source: Signal<'a, StreamEvent<T>>, mapper: M) -> Self {
+        Self::with_store(world, source, mapper, BufferedStore::new())
+    }
+
+    pub fn bounded(
+        world: World,
+        source: Signal<'a, StreamEvent<T>>,
+        mapper: M,
+        capacity: usize,
+        policy: OverflowPolicy<R>,
+    ) -> Self {
+        Self::with_store(
+            world,
+            source,
+            mapper,
+            BufferedStore::bounded(capacity, policy),
+        )
+    }
+
+    fn with_store(
+        world: World,
+        source: Signal<'a, StreamEvent<T>>,
+        mapper: M,
+        values: BufferedStore<R>,
+    ) -> Self {
         let own_node = NodeState::new(world);
         Mapper {
             source: ParentSignal::new(source, own_node.node()),
-            values: BufferedStore::new(),
+            values,
             mapper,
             own_node,
         }
     }
 }
 
-impl<'a, T: Data + 'a, R: Data + 'a, M: Fn(T) -> R + 'a> ComputationCore for Mapper<'a, T, R, M> {
-    type ComputationResult = Option<R>;
+impl<'a, T: Data + 'a, R: Data + 'a, M: Fn(T) -> R + 'a> Operator for Mapper<'a, T, R, M> {
+    type Output = StreamEvent<R>;
+    type Store = BufferedStore<R>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.own_node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.values
+    }
 
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
         if self.own_node.is_dirty() {
             self.own_node.clean();
-            while let Some(next) = self.source.compute() {
-                self.values.push((self.mapper)(next))
+            let values = &mut self.values;
+            let mapper = &self.mapper;
+            if self.source.drain_items(|next| values.push(mapper(next))) {
+                values.complete()
             }
         }
         self.values.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.values.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.values.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.own_node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.own_node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.own_node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.own_node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.own_node.node()
-    }
 }
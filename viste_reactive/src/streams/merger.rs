@@ -0,0 +1,51 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Merger<'a, T: Data + 'a> {
+    outer: ParentStreamSignal<'a, StreamSignal<'a, T>>,
+    inners: Vec<ParentStreamSignal<'a, T>>,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Merger<'a, T> {
+    pub fn new(world: World, outer: StreamSignal<'a, StreamSignal<'a, T>>) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            outer: ParentSignal::new(outer.0, node.node()),
+            inners: Vec::new(),
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Merger<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let own_node = self.node.node();
+            let inners = &mut self.inners;
+            let outer_complete = self
+                .outer
+                .drain_items(|inner| inners.push(ParentSignal::new(inner.0, own_node)));
+            let store = &mut self.store;
+            inners.retain_mut(|inner| !inner.drain_items(|next| store.push(next)));
+            if outer_complete && inners.is_empty() {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
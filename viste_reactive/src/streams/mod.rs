@@ -1,12 +1,38 @@
+pub mod bounded;
+pub mod buffer_until;
 pub mod cached;
 pub mod changed;
+pub mod chunks;
 pub mod combine_mapper;
+pub mod concatenator;
 pub mod counter;
+pub mod debounce;
+pub mod delay;
+pub mod differ;
+pub mod enumerate;
 pub mod filter;
 pub mod filter_mapper;
 pub mod from_iter;
+pub mod gate;
+pub mod hold;
+pub mod interval;
 pub mod last;
 pub mod many;
 pub mod mapper;
+pub mod merger;
+pub mod pairwise;
 pub mod portal;
+pub mod replay;
+pub mod router;
+pub mod sampler;
+pub mod scanner;
+pub mod skip;
+pub mod skip_while;
+pub mod switcher;
+pub mod take;
+pub mod take_while;
+pub mod throttle;
+pub mod timeout;
+pub mod windows;
+pub mod with_latest;
 pub mod zip_mapper;
@@ -0,0 +1,51 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Pairwise<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    previous: Option<T>,
+    store: BufferedStore<(T, T)>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Pairwise<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            previous: None,
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Pairwise<'a, T> {
+    type Output = StreamEvent<(T, T)>;
+    type Store = BufferedStore<(T, T)>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let store = &mut self.store;
+            let previous = &mut self.previous;
+            let complete = self.source.drain_items(|next| {
+                if let Some(prev) = previous.replace(next.cheap_clone()) {
+                    store.push((prev, next))
+                }
+            });
+            if complete {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::stores::{BufferedStore, Store};
+use crate::stores::{BufferOverflow, BufferedStore, OverflowPolicy};
 use crate::*;
 
 pub struct Portal<T: Data> {
@@ -6,6 +6,7 @@ pub struct Portal<T: Data> {
     node: NodeState,
 }
 
+#[allow(clippy::extra_unused_lifetimes)]
 impl<'a, T: Data + 'a> Portal<T> {
     pub fn new(world: World) -> Self {
         Portal {
@@ -14,45 +15,45 @@ impl<'a, T: Data + 'a> Portal<T> {
         }
     }
 
+    pub fn bounded(world: World, capacity: usize, policy: OverflowPolicy<T>) -> Self {
+        Portal {
+            store: BufferedStore::bounded(capacity, policy),
+            node: NodeState::new(world),
+        }
+    }
+
     pub fn send(&mut self, value: T) {
         self.store.push(value);
         self.node.mark_dirty(DirtyingCause::External)
     }
-}
-
-impl<'a, T: Data + 'a> ComputationCore for Portal<T> {
-    type ComputationResult = Option<T>;
-
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
-        self.node.clean();
-        self.store.read(reader)
-    }
 
-    fn create_reader(&mut self) -> ReaderToken {
-        self.store.create_reader()
+    pub fn try_send(&mut self, value: T) -> Result<(), BufferOverflow<T>> {
+        self.store.try_push(value)?;
+        self.node.mark_dirty(DirtyingCause::External);
+        Ok(())
     }
 
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.store.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
+    pub fn complete(&mut self) {
+        self.store.complete();
+        self.node.mark_dirty(DirtyingCause::External)
     }
+}
 
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
+#[allow(clippy::extra_unused_lifetimes)]
+impl<'a, T: Data + 'a> Operator for Portal<T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
 
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
+    fn node_state(&self) -> &NodeState {
+        &self.node
     }
 
-    fn world(&self) -> World {
-        self.node.world().clone()
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
     }
 
-    fn node(&self) -> NodeIndex {
-        self.node.node()
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        self.node.clean();
+        self.store.read(reader)
     }
 }
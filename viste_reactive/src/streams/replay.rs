@@ -0,0 +1,69 @@
+use crate::stores::{ReplayStore, Store};
+use crate::*;
+
+pub struct Replay<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    store: ReplayStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Replay<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, count: usize) -> Self {
+        let node = NodeState::new(world);
+        let source = ParentSignal::new(source.0, node.node());
+        Self {
+            source,
+            store: ReplayStore::new(count),
+            node,
+        }
+    }
+
+    fn pull(&mut self) {
+        if self.is_dirty() {
+            self.node.clean();
+            let store = &mut self.store;
+            if self.source.drain_items(|next| store.push(next)) {
+                store.complete()
+            }
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> ComputationCore for Replay<'a, T> {
+    type ComputationResult = StreamEvent<T>;
+
+    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+        self.pull();
+        self.store.read(reader)
+    }
+
+    fn create_reader(&mut self) -> ReaderToken {
+        //Values that were sent before the reader existed belong to the history it is seeded with
+        self.pull();
+        self.store.create_reader()
+    }
+
+    fn destroy_reader(&mut self, reader: ReaderToken) {
+        self.store.destroy_reader(reader)
+    }
+
+    fn add_dependency(&mut self, child: NodeIndex) {
+        self.node.add_dependency(child)
+    }
+
+    fn remove_dependency(&mut self, child: NodeIndex) {
+        self.node.remove_dependency(child)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.node.is_dirty()
+    }
+
+    fn world(&self) -> World {
+        self.node.world().clone()
+    }
+
+    fn node(&self) -> NodeIndex {
+        self.node.node()
+    }
+}
@@ -0,0 +1,243 @@
+use crate::stores::{BufferedStore, Store};
+use crate::*;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+// Number of items kept for a key that currently has no reader
+pub const PENDING_LIMIT: usize = 1024;
+
+// Reads the source once and distributes every item into the store of its key.
+// Items for a key without a reader are kept, up to PENDING_LIMIT with the oldest dropped first,
+// until a reader of that key is created. They are discarded once every group of the key is dropped.
+// Every key is announced on the keys store the first time the router sees it.
+pub struct Router<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    key_func: Box<dyn Fn(&T) -> K + 'a>,
+    stores: HashMap<K, BufferedStore<T>>,
+    pending: HashMap<K, VecDeque<T>>,
+    seen: HashSet<K>,
+    groups: HashMap<K, usize>,
+    keys: BufferedStore<K>,
+    complete: bool,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> Router<'a, T, K> {
+    pub fn new<F: Fn(&T) -> K + 'a>(
+        world: World,
+        source: StreamSignal<'a, T>,
+        key_func: F,
+    ) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            key_func: Box::new(key_func),
+            stores: HashMap::new(),
+            pending: HashMap::new(),
+            seen: HashSet::new(),
+            groups: HashMap::new(),
+            keys: BufferedStore::new(),
+            complete: false,
+            node,
+        }
+    }
+
+    fn pull(&mut self) {
+        if self.node.is_dirty() {
+            self.node.clean();
+            let stores = &mut self.stores;
+            let pending = &mut self.pending;
+            let seen = &mut self.seen;
+            let keys = &mut self.keys;
+            let key_func = &self.key_func;
+            let complete = self.source.drain_items(|next| {
+                let key = key_func(&next);
+                if !seen.contains(&key) {
+                    seen.insert(key.cheap_clone());
+                    keys.push(key.cheap_clone());
+                }
+                match stores.get_mut(&key) {
+                    Some(store) => store.push(next),
+                    None => {
+                        let items = pending.entry(key).or_default();
+                        if items.len() == PENDING_LIMIT {
+                            items.pop_front();
+                        }
+                        items.push_back(next)
+                    }
+                }
+            });
+            if complete {
+                self.complete = true;
+                stores.values_mut().for_each(BufferedStore::complete);
+                keys.complete();
+            }
+        }
+    }
+
+    fn store(&mut self, key: K) -> &mut BufferedStore<T> {
+        self.stores.entry(key).or_default()
+    }
+
+    fn create_reader(&mut self, key: K) -> ReaderToken {
+        // Items still waiting in the source belong to the pending items of the new reader
+        self.pull();
+        let pending = self.pending.remove(&key);
+        let complete = self.complete;
+        let store = self.store(key);
+        let reader = store.create_reader();
+        pending
+            .into_iter()
+            .flatten()
+            .for_each(|item| store.push(item));
+        if complete {
+            store.complete()
+        }
+        reader
+    }
+
+    // Once the last reader of a key is gone its items are buffered again until the next reader
+    fn destroy_reader(&mut self, key: &K, reader: ReaderToken) {
+        if let Some(store) = self.stores.get_mut(key) {
+            store.destroy_reader(reader);
+            if store.readers() == 0 {
+                self.stores.remove(key);
+            }
+        }
+    }
+}
+
+pub struct Routed<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> {
+    router: Rc<RefCell<Router<'a, T, K>>>,
+    key: K,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> Routed<'a, T, K> {
+    pub fn new(world: World, router: Rc<RefCell<Router<'a, T, K>>>, key: K) -> Self {
+        let node = NodeState::new(world);
+        {
+            let mut r = router.borrow_mut();
+            r.node.add_dependency(node.node());
+            *r.groups.entry(key.cheap_clone()).or_default() += 1;
+        }
+        Self { router, key, node }
+    }
+}
+
+impl<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> Drop for Routed<'a, T, K> {
+    fn drop(&mut self) {
+        let mut router = self.router.borrow_mut();
+        router.node.remove_dependency(self.node.node());
+        let groups = router
+            .groups
+            .get_mut(&self.key)
+            .expect("Group must be counted");
+        *groups -= 1;
+        if *groups == 0 {
+            router.groups.remove(&self.key);
+            router.pending.remove(&self.key);
+        }
+    }
+}
+
+impl<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> ComputationCore for Routed<'a, T, K> {
+    type ComputationResult = StreamEvent<T>;
+
+    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+        self.node.clean();
+        let mut router = self.router.borrow_mut();
+        router.pull();
+        router.store(self.key.clone()).read(reader)
+    }
+
+    fn create_reader(&mut self) -> ReaderToken {
+        self.router.borrow_mut().create_reader(self.key.clone())
+    }
+
+    fn destroy_reader(&mut self, reader: ReaderToken) {
+        self.router.borrow_mut().destroy_reader(&self.key, reader)
+    }
+
+    fn add_dependency(&mut self, child: NodeIndex) {
+        self.node.add_dependency(child)
+    }
+
+    fn remove_dependency(&mut self, child: NodeIndex) {
+        self.node.remove_dependency(child)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.node.is_dirty()
+    }
+
+    fn world(&self) -> World {
+        self.node.world().clone()
+    }
+
+    fn node(&self) -> NodeIndex {
+        self.node.node()
+    }
+}
+
+// Emits every key the router sees for the first time
+pub struct RoutedKeys<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> {
+    router: Rc<RefCell<Router<'a, T, K>>>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> RoutedKeys<'a, T, K> {
+    pub fn new(world: World, router: Rc<RefCell<Router<'a, T, K>>>) -> Self {
+        let node = NodeState::new(world);
+        router.borrow().node.add_dependency(node.node());
+        Self { router, node }
+    }
+}
+
+impl<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> Drop for RoutedKeys<'a, T, K> {
+    fn drop(&mut self) {
+        self.router
+            .borrow()
+            .node
+            .remove_dependency(self.node.node())
+    }
+}
+
+impl<'a, T: Data + 'a, K: Data + Hash + Eq + 'a> ComputationCore for RoutedKeys<'a, T, K> {
+    type ComputationResult = StreamEvent<K>;
+
+    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+        self.node.clean();
+        let mut router = self.router.borrow_mut();
+        router.pull();
+        router.keys.read(reader)
+    }
+
+    fn create_reader(&mut self) -> ReaderToken {
+        self.router.borrow_mut().keys.create_reader()
+    }
+
+    fn destroy_reader(&mut self, reader: ReaderToken) {
+        self.router.borrow_mut().keys.destroy_reader(reader)
+    }
+
+    fn add_dependency(&mut self, child: NodeIndex) {
+        self.node.add_dependency(child)
+    }
+
+    fn remove_dependency(&mut self, child: NodeIndex) {
+        self.node.remove_dependency(child)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.node.is_dirty()
+    }
+
+    fn world(&self) -> World {
+        self.node.world().clone()
+    }
+
+    fn node(&self) -> NodeIndex {
+        self.node.node()
+    }
+}
@@ -0,0 +1,49 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Sampler<'a, T: Data + 'a, U: Data + 'a> {
+    value: ParentCachedValueSignal<'a, T>,
+    trigger: ParentStreamSignal<'a, U>,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a, U: Data + 'a> Sampler<'a, T, U> {
+    pub fn new(world: World, value: ValueSignal<'a, T>, trigger: StreamSignal<'a, U>) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            value: ParentSignal::new(value.0, node.node()),
+            trigger: ParentSignal::new(trigger.0, node.node()),
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a, U: Data + 'a> Operator for Sampler<'a, T, U> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let (_, current) = self.value.compute();
+            let store = &mut self.store;
+            if self
+                .trigger
+                .drain_items(|_| store.push(current.cheap_clone()))
+            {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
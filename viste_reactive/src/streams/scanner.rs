@@ -0,0 +1,56 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Scanner<'a, T: Data + 'a, S: Data + 'a, F: Fn(S, T) -> S + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    // Only an option to allow using take() while scanning, may never be None
+    state: Option<S>,
+    scanner: F,
+    store: BufferedStore<S>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a, S: Data + 'a, F: Fn(S, T) -> S + 'a> Scanner<'a, T, S, F> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, initial: S, scanner: F) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            state: Some(initial),
+            scanner,
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a, S: Data + 'a, F: Fn(S, T) -> S + 'a> Operator for Scanner<'a, T, S, F> {
+    type Output = StreamEvent<S>;
+    type Store = BufferedStore<S>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let store = &mut self.store;
+            let state = &mut self.state;
+            let scanner = &self.scanner;
+            let complete = self.source.drain_items(|next| {
+                let old_state = state.take().expect("State of scanner may never be None");
+                let new_state = scanner(old_state, next);
+                store.push(new_state.cheap_clone());
+                *state = Some(new_state);
+            });
+            if complete {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
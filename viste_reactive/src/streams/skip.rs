@@ -0,0 +1,53 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Skip<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    remaining: usize,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Skip<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, count: usize) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            remaining: count,
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Skip<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let store = &mut self.store;
+            let remaining = &mut self.remaining;
+            let complete = self.source.drain_items(|next| {
+                if *remaining > 0 {
+                    *remaining -= 1;
+                } else {
+                    store.push(next)
+                }
+            });
+            if complete {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
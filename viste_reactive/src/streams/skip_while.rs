@@ -0,0 +1,55 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct SkipWhile<'a, T: Data + 'a, P: Fn(&T) -> bool + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    predicate: P,
+    skipping: bool,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a, P: Fn(&T) -> bool + 'a> SkipWhile<'a, T, P> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, predicate: P) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            predicate,
+            skipping: true,
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a, P: Fn(&T) -> bool + 'a> Operator for SkipWhile<'a, T, P> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let store = &mut self.store;
+            let skipping = &mut self.skipping;
+            let predicate = &self.predicate;
+            let complete = self.source.drain_items(|next| {
+                if !*skipping || !predicate(&next) {
+                    *skipping = false;
+                    store.push(next)
+                }
+            });
+            if complete {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
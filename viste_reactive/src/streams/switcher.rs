@@ -0,0 +1,64 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Switcher<'a, T: Data + 'a> {
+    outer: ParentStreamSignal<'a, StreamSignal<'a, T>>,
+    current: Option<ParentStreamSignal<'a, T>>,
+    current_complete: bool,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Switcher<'a, T> {
+    pub fn new(world: World, outer: StreamSignal<'a, StreamSignal<'a, T>>) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            outer: ParentSignal::new(outer.0, node.node()),
+            current: None,
+            current_complete: true,
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+
+    fn drain_current(&mut self) {
+        if let Some(current) = &mut self.current {
+            let store = &mut self.store;
+            self.current_complete = current.drain_items(|next| store.push(next));
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Switcher<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let mut latest = None;
+            let outer_complete = self.outer.drain_items(|inner| latest = Some(inner));
+            if let Some(inner) = latest {
+                // Items the previous stream produced before the switch are still delivered
+                self.drain_current();
+                match &mut self.current {
+                    Some(current) => current.set_parent(inner.0),
+                    None => self.current = Some(ParentSignal::new(inner.0, self.node.node())),
+                }
+            }
+            self.drain_current();
+            if outer_complete && self.current_complete {
+                self.store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
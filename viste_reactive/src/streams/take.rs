@@ -0,0 +1,69 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Take<'a, T: Data + 'a> {
+    //Released once enough items were taken or the source completes
+    source: Option<ParentStreamSignal<'a, T>>,
+    remaining: usize,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Take<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, count: usize) -> Self {
+        let node = NodeState::new(world);
+        let mut store = BufferedStore::new();
+        let source = if count > 0 {
+            Some(ParentSignal::new(source.0, node.node()))
+        } else {
+            store.complete();
+            None
+        };
+        Self {
+            source,
+            remaining: count,
+            store,
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Take<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            if let Some(source) = &mut self.source {
+                let mut complete = false;
+                while self.remaining > 0 {
+                    match source.compute() {
+                        StreamEvent::Item(next) => {
+                            self.remaining -= 1;
+                            self.store.push(next)
+                        }
+                        StreamEvent::Pending => break,
+                        StreamEvent::Complete => {
+                            complete = true;
+                            break;
+                        }
+                    }
+                }
+                if complete || self.remaining == 0 {
+                    self.store.complete();
+                    self.source = None;
+                }
+            }
+        }
+        self.store.read(reader)
+    }
+}
@@ -0,0 +1,55 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct TakeWhile<'a, T: Data + 'a, P: Fn(&T) -> bool + 'a> {
+    //Released once the predicate fails or the source completes
+    source: Option<ParentStreamSignal<'a, T>>,
+    predicate: P,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a, P: Fn(&T) -> bool + 'a> TakeWhile<'a, T, P> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, predicate: P) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: Some(ParentSignal::new(source.0, node.node())),
+            predicate,
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a, P: Fn(&T) -> bool + 'a> Operator for TakeWhile<'a, T, P> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            if let Some(source) = &mut self.source {
+                let complete = loop {
+                    match source.compute() {
+                        StreamEvent::Item(next) if (self.predicate)(&next) => self.store.push(next),
+                        StreamEvent::Item(_) | StreamEvent::Complete => break true,
+                        StreamEvent::Pending => break false,
+                    }
+                };
+                if complete {
+                    self.store.complete();
+                    self.source = None;
+                }
+            }
+        }
+        self.store.read(reader)
+    }
+}
@@ -0,0 +1,57 @@
+use crate::stores::BufferedStore;
+use crate::*;
+use std::time::Duration;
+
+pub struct Throttle<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    period: Duration,
+    next_allowed: Duration,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Throttle<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, period: Duration) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            period,
+            next_allowed: Duration::from_secs(0),
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Throttle<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let now = self.node.world().now();
+            let period = self.period;
+            let next_allowed = &mut self.next_allowed;
+            let store = &mut self.store;
+            let complete = self.source.drain_items(|next| {
+                if now >= *next_allowed {
+                    *next_allowed = now + period;
+                    store.push(next)
+                }
+            });
+            if complete {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
@@ -0,0 +1,65 @@
+use crate::stores::BufferedStore;
+use crate::*;
+use std::time::Duration;
+
+pub struct Timeout<'a, T: Data + 'a> {
+    //Released once the stream completes or times out
+    source: Option<ParentStreamSignal<'a, T>>,
+    period: Duration,
+    deadline: Duration,
+    store: BufferedStore<T>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Timeout<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, period: Duration) -> Self {
+        let node = NodeState::new(world);
+        let deadline = node.world().now() + period;
+        node.schedule(deadline);
+        Self {
+            source: Some(ParentSignal::new(source.0, node.node())),
+            period,
+            deadline,
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Timeout<'a, T> {
+    type Output = StreamEvent<T>;
+    type Store = BufferedStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            if let Some(source) = &mut self.source {
+                let now = self.node.world().now();
+                let store = &mut self.store;
+                let mut received = false;
+                let complete = source.drain_items(|next| {
+                    received = true;
+                    store.push(next)
+                });
+                if received {
+                    self.deadline = now + self.period;
+                    self.node.schedule(self.deadline);
+                }
+                //Completes the stream if no item arrived within the period
+                if complete || now >= self.deadline {
+                    self.store.complete();
+                    self.source = None;
+                }
+            }
+        }
+        self.store.read(reader)
+    }
+}
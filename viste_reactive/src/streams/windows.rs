@@ -0,0 +1,59 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct Windows<'a, T: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    size: usize,
+    window: VecDeque<T>,
+    store: BufferedStore<Vec<T>>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a> Windows<'a, T> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, size: usize) -> Self {
+        assert!(size > 0, "Window size must be at least 1");
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            size,
+            window: VecDeque::with_capacity(size),
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a> Operator for Windows<'a, T> {
+    type Output = StreamEvent<Vec<T>>;
+    type Store = BufferedStore<Vec<T>>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let store = &mut self.store;
+            let window = &mut self.window;
+            let size = self.size;
+            let complete = self.source.drain_items(|next| {
+                if window.len() == size {
+                    window.pop_front();
+                }
+                window.push_back(next);
+                if window.len() == size {
+                    store.push(window.iter().map(|t| t.cheap_clone()).collect())
+                }
+            });
+            if complete {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
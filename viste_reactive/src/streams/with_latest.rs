@@ -0,0 +1,49 @@
+use crate::stores::BufferedStore;
+use crate::*;
+
+pub struct WithLatest<'a, T: Data + 'a, V: Data + 'a> {
+    source: ParentStreamSignal<'a, T>,
+    value: ParentCachedValueSignal<'a, V>,
+    store: BufferedStore<(T, V)>,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a, V: Data + 'a> WithLatest<'a, T, V> {
+    pub fn new(world: World, source: StreamSignal<'a, T>, value: ValueSignal<'a, V>) -> Self {
+        let node = NodeState::new(world);
+        Self {
+            source: ParentSignal::new(source.0, node.node()),
+            value: ParentSignal::new(value.0, node.node()),
+            store: BufferedStore::new(),
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a, V: Data + 'a> Operator for WithLatest<'a, T, V> {
+    type Output = StreamEvent<(T, V)>;
+    type Store = BufferedStore<(T, V)>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.is_dirty() {
+            self.node.clean();
+            let (_, latest) = self.value.compute();
+            let store = &mut self.store;
+            if self
+                .source
+                .drain_items(|next| store.push((next, latest.cheap_clone())))
+            {
+                store.complete()
+            }
+        }
+        self.store.read(reader)
+    }
+}
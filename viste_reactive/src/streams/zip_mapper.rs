@@ -1,4 +1,4 @@
-use crate::stores::{BufferedStore, Store};
+use crate::stores::BufferedStore;
 use crate::*;
 
 pub struct ZipMapper<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, M: Fn(I1, I2) -> O + 'a> {
@@ -35,61 +35,53 @@ impl<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, M: Fn(I1, I2) -> O + 'a>
     }
 }
 
-impl<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, M: Fn(I1, I2) -> O + 'a> ComputationCore
+impl<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, M: Fn(I1, I2) -> O + 'a> Operator
     for ZipMapper<'a, I1, I2, O, M>
 {
-    type ComputationResult = Option<O>;
+    type Output = StreamEvent<O>;
+    type Store = BufferedStore<O>;
 
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
         if self.is_dirty() {
             self.node.clean();
             loop {
-                let value1 = self.cached_value1.take().or_else(|| self.source1.compute());
-                let value2 = self.cached_value2.take().or_else(|| self.source2.compute());
+                let value1 = match self.cached_value1.take() {
+                    Some(v1) => StreamEvent::Item(v1),
+                    None => self.source1.compute(),
+                };
+                let value2 = match self.cached_value2.take() {
+                    Some(v2) => StreamEvent::Item(v2),
+                    None => self.source2.compute(),
+                };
                 match (value1, value2) {
-                    (Some(v1), Some(v2)) => {
+                    (StreamEvent::Item(v1), StreamEvent::Item(v2)) => {
                         self.store.push((self.mapper)(v1, v2));
                     }
-                    (Some(v1), None) => {
+                    //No more pairs can be formed once either side is exhausted
+                    (StreamEvent::Complete, _) | (_, StreamEvent::Complete) => {
+                        self.store.complete();
+                        break;
+                    }
+                    (StreamEvent::Item(v1), StreamEvent::Pending) => {
                         self.cached_value1 = Some(v1);
                         break;
                     }
-                    (None, Some(v2)) => {
+                    (StreamEvent::Pending, StreamEvent::Item(v2)) => {
                         self.cached_value2 = Some(v2);
                         break;
                     }
-                    (None, None) => break,
+                    (StreamEvent::Pending, StreamEvent::Pending) => break,
                 }
             }
         }
         self.store.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.store.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.store.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
@@ -0,0 +1,59 @@
+use std::time::{Duration, Instant};
+
+// Time based operators take the current time when they pull new items from their source,
+// so the graph should be read after sending values, e.g. once per frame after World::tick.
+
+pub trait Clock {
+    fn now(&self) -> Duration;
+    fn advance(&mut self, by: Duration);
+}
+
+// Only moves when advanced manually, which makes time based graphs deterministic
+#[derive(Debug, Default, Copy, Clone)]
+pub struct VirtualClock {
+    now: Duration,
+}
+
+impl VirtualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Duration {
+        self.now
+    }
+
+    fn advance(&mut self, by: Duration) {
+        self.now += by;
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct InstantClock {
+    origin: Instant,
+}
+
+impl InstantClock {
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for InstantClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for InstantClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    // Follows the system time, so advancing only means the time is checked again
+    fn advance(&mut self, _by: Duration) {}
+}
@@ -1,4 +1,4 @@
-use crate::stores::{SingleValueStore, Store};
+use crate::stores::SingleValueStore;
 use crate::*;
 
 pub struct Binder<'a, I: Data + 'a, O: Data + 'a, B: Fn(I) -> ValueSignal<'a, O> + 'a> {
@@ -27,12 +27,21 @@ impl<'a, I: Data + 'a, O: Data + 'a, B: Fn(I) -> ValueSignal<'a, O> + 'a> Binder
     }
 }
 
-impl<'a, I: Data + 'a, O: Data + 'a, B: Fn(I) -> ValueSignal<'a, O> + 'a> ComputationCore
+impl<'a, I: Data + 'a, O: Data + 'a, B: Fn(I) -> ValueSignal<'a, O> + 'a> Operator
     for Binder<'a, I, O, B>
 {
-    type ComputationResult = SingleComputationResult<O>;
+    type Output = SingleComputationResult<O>;
+    type Store = SingleValueStore<O>;
 
-    fn compute(&mut self, reader: ReaderToken) -> SingleComputationResult<O> {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.current_value
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> SingleComputationResult<O> {
         if self.node.is_dirty() {
             self.node.clean();
             if let SingleComputationResult::Changed(new_source) = self.parent.compute() {
@@ -45,34 +54,6 @@ impl<'a, I: Data + 'a, O: Data + 'a, B: Fn(I) -> ValueSignal<'a, O> + 'a> Comput
         }
         self.current_value.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.current_value.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.current_value.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
 
 pub struct Binder2<
@@ -122,11 +103,20 @@ impl<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, B: Fn(I1, I2) -> ValueSigna
 }
 
 impl<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, B: Fn(I1, I2) -> ValueSignal<'a, O> + 'a>
-    ComputationCore for Binder2<'a, I1, I2, O, B>
+    Operator for Binder2<'a, I1, I2, O, B>
 {
-    type ComputationResult = SingleComputationResult<O>;
+    type Output = SingleComputationResult<O>;
+    type Store = SingleValueStore<O>;
 
-    fn compute(&mut self, reader: ReaderToken) -> SingleComputationResult<O> {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.current_value
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> SingleComputationResult<O> {
         if self.node.is_dirty() {
             self.node.clean();
             let (changed1, s1) = self.parent1.compute();
@@ -141,32 +131,4 @@ impl<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, B: Fn(I1, I2) -> ValueSigna
         }
         self.current_value.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.current_value.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.current_value.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
@@ -0,0 +1,102 @@
+use crate::stores::SingleValueStore;
+use crate::*;
+use std::collections::HashSet;
+
+trait Dependency {
+    fn changed(&mut self) -> bool;
+}
+
+impl<'a, T: Data + 'a> Dependency for ParentValueSignal<'a, T> {
+    fn changed(&mut self) -> bool {
+        matches!(self.compute(), SingleComputationResult::Changed(_))
+    }
+}
+
+type Dependencies<'a> = HashMap<NodeIndex, Box<dyn Dependency + 'a>>;
+
+// Records every signal read during one evaluation of a computed signal
+pub struct ComputeContext<'c, 'a> {
+    node: NodeIndex,
+    dependencies: &'c mut Dependencies<'a>,
+    read: HashSet<NodeIndex>,
+}
+
+impl<'c, 'a> ComputeContext<'c, 'a> {
+    pub fn get<T: Data + 'a>(&mut self, signal: &ValueSignal<'a, T>) -> T {
+        let parent = signal.signal().node();
+        if self.read.insert(parent) && !self.dependencies.contains_key(&parent) {
+            let mut dependency: ParentValueSignal<T> =
+                ParentSignal::new(signal.signal().clone(), self.node);
+            // The first read of a new reader is always a change
+            dependency.changed();
+            self.dependencies.insert(parent, Box::new(dependency));
+        }
+        read_once(signal)
+    }
+}
+
+pub struct Computed<'a, T: Data + 'a, F: Fn(&mut ComputeContext<'_, 'a>) -> T + 'a> {
+    dependencies: Dependencies<'a>,
+    current_value: SingleValueStore<T>,
+    compute: F,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a, F: Fn(&mut ComputeContext<'_, 'a>) -> T + 'a> Computed<'a, T, F> {
+    pub fn new(world: World, compute: F) -> Self {
+        let node = NodeState::new(world);
+        info!("Computed signal created: {}", node.node());
+        let mut dependencies = HashMap::new();
+        let initial = Self::evaluate(&compute, node.node(), &mut dependencies);
+        Self {
+            dependencies,
+            current_value: SingleValueStore::new(initial),
+            compute,
+            node,
+        }
+    }
+
+    // Dependencies that were not read during this evaluation are dropped, which removes their edges
+    fn evaluate(compute: &F, node: NodeIndex, dependencies: &mut Dependencies<'a>) -> T {
+        let mut cx = ComputeContext {
+            node,
+            dependencies,
+            read: HashSet::new(),
+        };
+        let value = compute(&mut cx);
+        let read = cx.read;
+        dependencies.retain(|parent, _| read.contains(parent));
+        value
+    }
+}
+
+impl<'a, T: Data + 'a, F: Fn(&mut ComputeContext<'_, 'a>) -> T + 'a> Operator
+    for Computed<'a, T, F>
+{
+    type Output = SingleComputationResult<T>;
+    type Store = SingleValueStore<T>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.current_value
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> SingleComputationResult<T> {
+        if self.node.is_dirty() {
+            self.node.clean();
+            // Every dependency is polled, so none of them keeps a stale change for the next run
+            let mut changed = false;
+            for dependency in self.dependencies.values_mut() {
+                changed |= dependency.changed();
+            }
+            if changed {
+                let value = Self::evaluate(&self.compute, self.node.node(), &mut self.dependencies);
+                self.current_value.set_value(value);
+            }
+        }
+        self.current_value.read(reader)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::stores::{SingleValueStore, Store};
+use crate::stores::SingleValueStore;
 use crate::*;
 
 pub struct Constant<T: Data> {
@@ -17,34 +17,19 @@ impl<T: Data> Constant<T> {
     }
 }
 
-impl<T: Data> ComputationCore for Constant<T> {
-    type ComputationResult = SingleComputationResult<T>;
+impl<T: Data> Operator for Constant<T> {
+    type Output = SingleComputationResult<T>;
+    type Store = SingleValueStore<T>;
 
-    fn compute(&mut self, reader: ReaderToken) -> SingleComputationResult<T> {
-        self.value.read(reader)
-    }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.value.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.value.destroy_reader(reader)
+    fn node_state(&self) -> &NodeState {
+        &self.node
     }
 
-    fn add_dependency(&mut self, _child: NodeIndex) {}
-
-    fn remove_dependency(&mut self, _child: NodeIndex) {}
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.value
     }
 
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
+    fn evaluate(&mut self, reader: ReaderToken) -> SingleComputationResult<T> {
+        self.value.read(reader)
     }
 }
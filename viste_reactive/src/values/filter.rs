@@ -1,4 +1,4 @@
-use crate::stores::{SingleValueStore, Store};
+use crate::stores::SingleValueStore;
 use crate::*;
 
 pub struct Filter<'a, T: Data, F: Fn(&T) -> bool + 'a> {
@@ -22,10 +22,19 @@ impl<'a, T: Data, F: Fn(&T) -> bool + 'a> Filter<'a, T, F> {
     }
 }
 
-impl<'a, T: Data + 'a, F: Fn(&T) -> bool + 'a> ComputationCore for Filter<'a, T, F> {
-    type ComputationResult = SingleComputationResult<T>;
+impl<'a, T: Data + 'a, F: Fn(&T) -> bool + 'a> Operator for Filter<'a, T, F> {
+    type Output = SingleComputationResult<T>;
+    type Store = SingleValueStore<T>;
 
-    fn compute(&mut self, reader: ReaderToken) -> SingleComputationResult<T> {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.current_value
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> SingleComputationResult<T> {
         if self.node.is_dirty() {
             self.node.clean();
             if let SingleComputationResult::Changed(new_source) = self.source.compute() {
@@ -36,32 +45,4 @@ impl<'a, T: Data + 'a, F: Fn(&T) -> bool + 'a> ComputationCore for Filter<'a, T,
         }
         self.current_value.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.current_value.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.current_value.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
@@ -0,0 +1,70 @@
+use crate::stores::SingleValueStore;
+use crate::*;
+
+pub struct Focus<'a, T: Data + 'a, U: Data + 'a, G: Fn(&T) -> U + 'a, S: Fn(&mut T, U) + 'a> {
+    source: ParentCachedValueSignal<'a, T>,
+    parent: Rc<RefCell<dyn Writable<T> + 'a>>,
+    current_value: SingleValueStore<U>,
+    get: G,
+    set: S,
+    node: NodeState,
+}
+
+impl<'a, T: Data + 'a, U: Data + 'a, G: Fn(&T) -> U + 'a, S: Fn(&mut T, U) + 'a>
+    Focus<'a, T, U, G, S>
+{
+    pub fn new(
+        world: World,
+        source: ValueSignal<'a, T>,
+        parent: Rc<RefCell<dyn Writable<T> + 'a>>,
+        get: G,
+        set: S,
+    ) -> Self {
+        let node = NodeState::new(world);
+        let mut source: ParentCachedValueSignal<T> = ParentSignal::new(source.0, node.node());
+        let (_, whole) = source.compute();
+        Self {
+            current_value: SingleValueStore::new(get(&whole)),
+            source,
+            parent,
+            get,
+            set,
+            node,
+        }
+    }
+}
+
+impl<'a, T: Data + 'a, U: Data + 'a, G: Fn(&T) -> U + 'a, S: Fn(&mut T, U) + 'a> Operator
+    for Focus<'a, T, U, G, S>
+{
+    type Output = SingleComputationResult<U>;
+    type Store = SingleValueStore<U>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.current_value
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
+        if self.node.is_dirty() {
+            self.node.clean();
+            // Readers only see a change when the focused part differs
+            let (_, whole) = self.source.compute();
+            self.current_value.set_value((self.get)(&whole));
+        }
+        self.current_value.read(reader)
+    }
+}
+
+impl<'a, T: Data + 'a, U: Data + 'a, G: Fn(&T) -> U + 'a, S: Fn(&mut T, U) + 'a> Writable<U>
+    for Focus<'a, T, U, G, S>
+{
+    fn write(&mut self, part: U) {
+        let (_, mut whole) = self.source.compute();
+        (self.set)(&mut whole, part);
+        self.parent.borrow_mut().write(whole)
+    }
+}
@@ -1,8 +1,11 @@
-use crate::stores::{SingleValueStore, Store};
+use crate::snapshot::Stateful;
+use crate::stores::SingleValueStore;
 use crate::*;
+use std::any::Any;
 
 pub struct Folder<'a, T: Data + 'a, V: Data + 'a, F: Fn(V, T) -> V + 'a> {
-    source: ParentSignal<'a, Option<T>, Option<T>, StreamReader<'a, T>>,
+    //Released once the source stream completes
+    source: Option<ParentStreamSignal<'a, T>>,
     store: SingleValueStore<V>,
     current_value: Option<V>,
     folder: F,
@@ -14,7 +17,7 @@ impl<'a, T: Data + 'a, V: Data + 'a, F: Fn(V, T) -> V + 'a> Folder<'a, T, V, F>
         let node = NodeState::new(world);
         let source = ParentSignal::new(source.0, node.node());
         Self {
-            source,
+            source: Some(source),
             store: SingleValueStore::new(initial.cheap_clone()),
             folder,
             current_value: Some(initial),
@@ -23,24 +26,53 @@ impl<'a, T: Data + 'a, V: Data + 'a, F: Fn(V, T) -> V + 'a> Folder<'a, T, V, F>
     }
 }
 
-impl<'a, T: Data + 'a, V: Data + 'a, F: Fn(V, T) -> V + 'a> ComputationCore
-    for Folder<'a, T, V, F>
-{
-    type ComputationResult = SingleComputationResult<V>;
+impl<'a, T: Data + 'a, V: Data + 'static, F: Fn(V, T) -> V + 'a> Stateful for Folder<'a, T, V, F> {
+    fn save(&self) -> Rc<dyn Any> {
+        Rc::new(self.store.get())
+    }
+
+    // Items that are still buffered in the source are folded on top of the restored value
+    fn restore(&mut self, state: &dyn Any) {
+        let value = state
+            .downcast_ref::<V>()
+            .expect("Snapshot value does not match the type of the folder");
+        self.current_value = Some(value.cheap_clone());
+        self.store.set_value(value.cheap_clone());
+        self.node.mark_dirty(DirtyingCause::External)
+    }
+}
+
+impl<'a, T: Data + 'a, V: Data + 'a, F: Fn(V, T) -> V + 'a> Operator for Folder<'a, T, V, F> {
+    type Output = SingleComputationResult<V>;
+    type Store = SingleValueStore<V>;
+
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.store
+    }
 
-    fn compute(&mut self, reader: ReaderToken) -> Self::ComputationResult {
+    fn evaluate(&mut self, reader: ReaderToken) -> Self::Output {
         if self.is_dirty() {
             self.node.clean();
             let mut changed = false;
             // self.current_value is an option, but may never be None
             // it is only an option to allow using take() and avoid unnecessary cloning during folding
-            while let Some(next) = self.source.compute() {
-                changed = true;
-                let old_value = self
-                    .current_value
-                    .take()
-                    .expect("Current value of folder may never be None");
-                self.current_value = Some((self.folder)(old_value, next));
+            if let Some(source) = &mut self.source {
+                let current_value = &mut self.current_value;
+                let folder = &self.folder;
+                let complete = source.drain_items(|next| {
+                    changed = true;
+                    let old_value = current_value
+                        .take()
+                        .expect("Current value of folder may never be None");
+                    *current_value = Some(folder(old_value, next));
+                });
+                if complete {
+                    self.source = None;
+                }
             }
             if changed {
                 self.store.set_value(
@@ -53,32 +85,4 @@ impl<'a, T: Data + 'a, V: Data + 'a, F: Fn(V, T) -> V + 'a> ComputationCore
         }
         self.store.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.store.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.store.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
@@ -1,4 +1,4 @@
-use crate::stores::{SingleValueStore, Store};
+use crate::stores::SingleValueStore;
 use crate::*;
 
 pub struct Mapper<'a, I: Data, O: Data, M: Fn(I) -> O + 'a> {
@@ -23,10 +23,19 @@ impl<'a, I: Data + 'a, O: Data + 'a, M: Fn(I) -> O + 'a> Mapper<'a, I, O, M> {
     }
 }
 
-impl<'a, I: Data + 'a, O: Data + 'a, M: Fn(I) -> O + 'a> ComputationCore for Mapper<'a, I, O, M> {
-    type ComputationResult = SingleComputationResult<O>;
+impl<'a, I: Data + 'a, O: Data + 'a, M: Fn(I) -> O + 'a> Operator for Mapper<'a, I, O, M> {
+    type Output = SingleComputationResult<O>;
+    type Store = SingleValueStore<O>;
 
-    fn compute(&mut self, reader: ReaderToken) -> SingleComputationResult<O> {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.current_value
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> SingleComputationResult<O> {
         if self.node.is_dirty() {
             self.node.clean();
             if let SingleComputationResult::Changed(new_source) = self.source.compute() {
@@ -35,34 +44,6 @@ impl<'a, I: Data + 'a, O: Data + 'a, M: Fn(I) -> O + 'a> ComputationCore for Map
         }
         self.current_value.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.current_value.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.current_value.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
 
 pub struct Mapper2<'a, I1: Data + 'a, I2: Data + 'a, O: Data + 'a, M: Fn(I1, I2) -> O + 'a> {
@@ -97,12 +78,21 @@ impl<'a, I1: Data, I2: Data, O: Data, M: Fn(I1, I2) -> O + 'a> Mapper2<'a, I1, I
     }
 }
 
-impl<'a, I1: Data, I2: Data, O: Data, M: Fn(I1, I2) -> O + 'a> ComputationCore
+impl<'a, I1: Data, I2: Data, O: Data, M: Fn(I1, I2) -> O + 'a> Operator
     for Mapper2<'a, I1, I2, O, M>
 {
-    type ComputationResult = SingleComputationResult<O>;
+    type Output = SingleComputationResult<O>;
+    type Store = SingleValueStore<O>;
 
-    fn compute(&mut self, reader: ReaderToken) -> SingleComputationResult<O> {
+    fn node_state(&self) -> &NodeState {
+        &self.node
+    }
+
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.current_value
+    }
+
+    fn evaluate(&mut self, reader: ReaderToken) -> SingleComputationResult<O> {
         if self.node.is_dirty() {
             self.node.clean();
             let (changed1, v1) = self.source1.compute();
@@ -113,32 +103,4 @@ impl<'a, I1: Data, I2: Data, O: Data, M: Fn(I1, I2) -> O + 'a> ComputationCore
         }
         self.current_value.read(reader)
     }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.current_value.create_reader()
-    }
-
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.current_value.destroy_reader(reader)
-    }
-
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
-
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
-    }
-
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
-    }
-
-    fn world(&self) -> World {
-        self.node.world().clone()
-    }
-
-    fn node(&self) -> NodeIndex {
-        self.node.node()
-    }
 }
@@ -1,7 +1,9 @@
 pub mod binder;
+pub mod computed;
 pub mod constant;
 pub mod filter;
 pub mod filter_mapper;
+pub mod focus;
 pub mod folder;
 pub mod mapper;
 pub mod mutable;
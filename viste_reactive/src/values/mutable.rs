@@ -1,6 +1,8 @@
-use crate::stores::{SingleValueStore, Store};
+use crate::snapshot::Stateful;
+use crate::stores::SingleValueStore;
 use crate::*;
 use log::info;
+use std::any::Any;
 
 pub struct Mutable<T: Data> {
     current_value: SingleValueStore<T>,
@@ -17,45 +19,49 @@ impl<T: Data> Mutable<T> {
         }
     }
 
+    pub fn get(&self) -> T {
+        self.current_value.get()
+    }
+
     pub fn set(&mut self, value: T) {
         self.current_value.set_value(value);
         self.node.mark_dirty(DirtyingCause::External)
     }
 }
 
-impl<T: Data> ComputationCore for Mutable<T> {
-    type ComputationResult = SingleComputationResult<T>;
-
-    fn compute(&mut self, reader: ReaderToken) -> SingleComputationResult<T> {
-        self.node.clean();
-        self.current_value.read(reader)
-    }
-
-    fn create_reader(&mut self) -> ReaderToken {
-        self.current_value.create_reader()
+impl<T: Data + 'static> Stateful for Mutable<T> {
+    fn save(&self) -> Rc<dyn Any> {
+        Rc::new(self.current_value.get())
     }
 
-    fn destroy_reader(&mut self, reader: ReaderToken) {
-        self.current_value.destroy_reader(reader)
+    fn restore(&mut self, state: &dyn Any) {
+        let value = state
+            .downcast_ref::<T>()
+            .expect("Snapshot value does not match the type of the mutable");
+        self.set(value.cheap_clone())
     }
+}
 
-    fn add_dependency(&mut self, child: NodeIndex) {
-        self.node.add_dependency(child)
-    }
+impl<T: Data> Operator for Mutable<T> {
+    type Output = SingleComputationResult<T>;
+    type Store = SingleValueStore<T>;
 
-    fn remove_dependency(&mut self, child: NodeIndex) {
-        self.node.remove_dependency(child)
+    fn node_state(&self) -> &NodeState {
+        &self.node
     }
 
-    fn is_dirty(&self) -> bool {
-        self.node.is_dirty()
+    fn store(&mut self) -> &mut Self::Store {
+        &mut self.current_value
     }
 
-    fn world(&self) -> World {
-        self.node.world().clone()
+    fn evaluate(&mut self, reader: ReaderToken) -> SingleComputationResult<T> {
+        self.node.clean();
+        self.current_value.read(reader)
     }
+}
 
-    fn node(&self) -> NodeIndex {
-        self.node.node()
+impl<T: Data> Writable<T> for Mutable<T> {
+    fn write(&mut self, value: T) {
+        self.set(value)
     }
 }